@@ -0,0 +1,48 @@
+//  ERRORS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:46:18
+//  Last edited:
+//    17 Oct 2026, 05:37:46
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the errors for the asset library.
+// 
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::path::PathBuf;
+
+
+/***** LIBRARY *****/
+/// Errors that relate to loading assets.
+#[derive(Debug)]
+pub enum AssetError {
+    /// Could not load an image file.
+    ImageLoadError{ path: PathBuf, err: image::ImageError },
+    /// Could not load an OBJ file.
+    ObjLoadError{ path: PathBuf, err: tobj::LoadError },
+    /// The loaded file did not contain any geometry.
+    EmptyMesh{ path: PathBuf },
+
+    /// Could not create or populate one of the buffers of a mesh.
+    MeshUploadError{ path: PathBuf, err: game_pip::Error },
+}
+
+impl Display for AssetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        use AssetError::*;
+        match self {
+            ImageLoadError{ path, err } => write!(f, "Could not load image '{}': {}", path.display(), err),
+            ObjLoadError{ path, err }   => write!(f, "Could not load OBJ file '{}': {}", path.display(), err),
+            EmptyMesh{ path }           => write!(f, "File '{}' does not contain any geometry", path.display()),
+
+            MeshUploadError{ path, err } => write!(f, "Could not upload mesh '{}' to the GPU: {}", path.display(), err),
+        }
+    }
+}
+
+impl Error for AssetError {}
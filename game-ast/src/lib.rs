@@ -0,0 +1,28 @@
+//  LIB.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:46:18
+//  Last edited:
+//    17 Oct 2026, 04:46:18
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Entrypoint to the asset library, which loads textures and meshes
+//!   from disk and uploads them to the GPU.
+// 
+
+// Define the submodules of this crate
+pub mod errors;
+pub mod vertex;
+pub mod texture;
+pub mod mesh;
+pub mod manager;
+
+// Pull some things into the crate namespace
+pub use errors::AssetError as Error;
+pub use vertex::MeshVertex;
+pub use texture::Texture;
+pub use mesh::{Mesh, MeshData};
+pub use manager::AssetManager;
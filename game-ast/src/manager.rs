@@ -0,0 +1,187 @@
+//  MANAGER.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:46:18
+//  Last edited:
+//    17 Oct 2026, 05:38:01
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the AssetManager, which loads assets relative to an
+//!   asset directory and caches them by path.
+// 
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use log::debug;
+use rust_vk::device::Device;
+use rust_vk::pools::memory::prelude::*;
+use rust_vk::pools::command::Pool as CommandPool;
+
+use game_gfx::RenderSystem;
+use game_gfx::registry::ResourceRegistry;
+
+pub use crate::errors::AssetError as Error;
+use crate::texture::Texture;
+use crate::mesh::{Mesh, MeshData};
+
+
+/***** LIBRARY *****/
+/// The AssetManager, which loads textures and meshes and keeps them around until they are unloaded.
+/// 
+/// Meshes are uploaded to the GPU immediately, and their buffers are owned by the RenderSystem's ResourceRegistry; pipelines bind them through the handles in the returned Meshes. Textures are decoded to RGBA8 and kept in CPU memory for now.
+pub struct AssetManager {
+    /// The directory that relative asset paths are resolved against.
+    root : PathBuf,
+
+    /// The Device where we upload meshes to.
+    device       : Rc<Device>,
+    /// The MemoryPool where we allocate mesh buffers.
+    memory_pool  : Rc<RefCell<dyn MemoryPool>>,
+    /// The CommandPool that we use to copy staging buffers.
+    command_pool : Rc<RefCell<CommandPool>>,
+    /// The ResourceRegistry that owns the uploaded buffers.
+    resources    : Rc<RefCell<ResourceRegistry>>,
+
+    /// The loaded meshes, by (resolved) path.
+    meshes   : HashMap<PathBuf, Rc<[Mesh]>>,
+    /// The loaded textures, by (resolved) path.
+    textures : HashMap<PathBuf, Rc<Texture>>,
+}
+
+impl AssetManager {
+    /// Constructor for the AssetManager.
+    /// 
+    /// # Arguments
+    /// - `root`: The directory that relative asset paths are resolved against (typically `Config::dirs.assets`).
+    /// - `render_system`: The RenderSystem whose Device, pools and ResourceRegistry we use to upload meshes.
+    /// 
+    /// # Returns
+    /// A new AssetManager without any assets loaded.
+    pub fn new<P: Into<PathBuf>>(root: P, render_system: &RenderSystem) -> Self {
+        Self {
+            root : root.into(),
+
+            device       : render_system.device().clone(),
+            memory_pool  : render_system.memory_pool().clone(),
+            command_pool : render_system.command_pool().clone(),
+            resources    : render_system.resources().clone(),
+
+            meshes   : HashMap::new(),
+            textures : HashMap::new(),
+        }
+    }
+
+
+
+    /// Resolves the given asset path against the asset directory.
+    #[inline]
+    fn resolve(&self, path: &Path) -> PathBuf { self.root.join(path) }
+
+    /// Loads the meshes in the given OBJ file and uploads them to the GPU, or returns them from the cache if they were loaded before.
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the OBJ file, relative to the asset directory (or absolute).
+    /// 
+    /// # Returns
+    /// The Meshes in the file, one per object.
+    /// 
+    /// # Errors
+    /// This function errors if the file could not be loaded or its meshes could not be uploaded.
+    pub fn load_mesh<P: AsRef<Path>>(&mut self, path: P) -> Result<Rc<[Mesh]>, Error> {
+        let path: PathBuf = self.resolve(path.as_ref());
+        if let Some(meshes) = self.meshes.get(&path) { return Ok(meshes.clone()); }
+
+        // Load and upload every mesh in the file
+        debug!("Loading mesh '{}'...", path.display());
+        let data: Vec<MeshData> = MeshData::load_obj(&path)?;
+        let mut meshes: Vec<Mesh> = Vec::with_capacity(data.len());
+        {
+            let mut resources = self.resources.borrow_mut();
+            for mesh in &data {
+                match Mesh::upload(&path, mesh, &self.device, &self.memory_pool, &self.command_pool, &mut resources) {
+                    Ok(mesh) => { meshes.push(mesh); },
+                    Err(err) => {
+                        // Don't leave the meshes we already uploaded behind in the registry
+                        for mesh in meshes {
+                            resources.vertex_buffers.remove(mesh.vertices);
+                            resources.index_buffers.remove(mesh.indices);
+                        }
+                        return Err(err);
+                    },
+                }
+            }
+        }
+
+        // Cache them
+        let meshes: Rc<[Mesh]> = meshes.into();
+        self.meshes.insert(path, meshes.clone());
+        Ok(meshes)
+    }
+
+    /// Loads the given texture, or returns it from the cache if it was loaded before.
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the image file, relative to the asset directory (or absolute).
+    /// 
+    /// # Returns
+    /// The decoded Texture.
+    /// 
+    /// # Errors
+    /// This function errors if the file could not be loaded.
+    pub fn load_texture<P: AsRef<Path>>(&mut self, path: P) -> Result<Rc<Texture>, Error> {
+        let path: PathBuf = self.resolve(path.as_ref());
+        if let Some(texture) = self.textures.get(&path) { return Ok(texture.clone()); }
+
+        // Load and cache it
+        debug!("Loading texture '{}'...", path.display());
+        let texture: Rc<Texture> = Rc::new(Texture::from_path(&path)?);
+        self.textures.insert(path, texture.clone());
+        Ok(texture)
+    }
+
+
+
+    /// Unloads the meshes from the given file, removing their buffers from the ResourceRegistry.
+    /// 
+    /// The buffers are only freed once the frames in flight that may use them are done, so this is safe to call at any time.
+    /// 
+    /// # Returns
+    /// Whether the meshes were loaded.
+    pub fn unload_mesh<P: AsRef<Path>>(&mut self, path: P) -> bool {
+        let path: PathBuf = self.resolve(path.as_ref());
+        let meshes: Rc<[Mesh]> = match self.meshes.remove(&path) {
+            Some(meshes) => meshes,
+            None         => { return false; }
+        };
+
+        // Remove the buffers from the registry
+        let mut resources = self.resources.borrow_mut();
+        for mesh in meshes.iter() {
+            resources.vertex_buffers.remove(mesh.vertices);
+            resources.index_buffers.remove(mesh.indices);
+        }
+        true
+    }
+
+    /// Unloads the given texture from the cache.
+    /// 
+    /// # Returns
+    /// Whether the texture was loaded.
+    #[inline]
+    pub fn unload_texture<P: AsRef<Path>>(&mut self, path: P) -> bool {
+        let path: PathBuf = self.resolve(path.as_ref());
+        self.textures.remove(&path).is_some()
+    }
+
+
+
+    /// Returns the directory that relative asset paths are resolved against.
+    #[inline]
+    pub fn root(&self) -> &Path { &self.root }
+}
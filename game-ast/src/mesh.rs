@@ -0,0 +1,145 @@
+//  MESH.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:46:18
+//  Last edited:
+//    17 Oct 2026, 05:37:46
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements loading meshes from OBJ files and uploading them to the
+//!   GPU.
+// 
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use rust_vk::device::Device;
+use rust_vk::pools::memory::prelude::*;
+use rust_vk::pools::memory::{IndexBuffer, VertexBuffer};
+use rust_vk::pools::command::Pool as CommandPool;
+
+use game_gfx::registry::{Handle, ResourceRegistry};
+use game_pip::buffers::{create_index_buffer, create_vertex_buffer};
+
+pub use crate::errors::AssetError as Error;
+use crate::vertex::MeshVertex;
+
+
+/***** LIBRARY *****/
+/// A mesh loaded from disk, but still in CPU memory.
+#[derive(Clone, Debug)]
+pub struct MeshData {
+    /// The name of the mesh (i.e., the object name in the file).
+    pub name     : String,
+    /// The vertices of the mesh.
+    pub vertices : Vec<MeshVertex>,
+    /// The indices of the mesh, three per triangle.
+    pub indices  : Vec<u32>,
+}
+
+impl MeshData {
+    /// Loads all meshes in the given OBJ file.
+    /// 
+    /// Faces are triangulated, and every vertex gets a single index (so positions, normals and texture coordinates are duplicated where needed).
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the OBJ file.
+    /// 
+    /// # Returns
+    /// One MeshData per object in the file. Objects without any triangles are skipped.
+    /// 
+    /// # Errors
+    /// This function errors if the file could not be read or parsed, or if it does not contain any geometry.
+    pub fn load_obj<P: AsRef<Path>>(path: P) -> Result<Vec<Self>, Error> {
+        let path: &Path = path.as_ref();
+
+        // Load the file
+        let (models, _) = match tobj::load_obj(path, &tobj::LoadOptions{ triangulate: true, single_index: true, ..Default::default() }) {
+            Ok(res)  => res,
+            Err(err) => { return Err(Error::ObjLoadError{ path: path.to_path_buf(), err }); }
+        };
+
+        // Convert the models to our own layout
+        let mut meshes: Vec<Self> = Vec::with_capacity(models.len());
+        for model in models {
+            let mesh: tobj::Mesh = model.mesh;
+            if mesh.indices.is_empty() { continue; }
+
+            // Collect the vertices
+            let n_vertices: usize = mesh.positions.len() / 3;
+            let mut vertices: Vec<MeshVertex> = Vec::with_capacity(n_vertices);
+            for i in 0..n_vertices {
+                let mut vertex = MeshVertex{ pos: [mesh.positions[3 * i], mesh.positions[3 * i + 1], mesh.positions[3 * i + 2]], ..Default::default() };
+                if mesh.normals.len() >= 3 * (i + 1) { vertex.normal = [mesh.normals[3 * i], mesh.normals[3 * i + 1], mesh.normals[3 * i + 2]]; }
+                if mesh.texcoords.len() >= 2 * (i + 1) { vertex.uv = [mesh.texcoords[2 * i], 1.0 - mesh.texcoords[2 * i + 1]]; }
+                vertices.push(vertex);
+            }
+
+            meshes.push(Self {
+                name    : model.name,
+                vertices,
+                indices : mesh.indices,
+            });
+        }
+
+        // Done
+        if meshes.is_empty() { return Err(Error::EmptyMesh{ path: path.to_path_buf() }); }
+        Ok(meshes)
+    }
+}
+
+
+
+/// A mesh that has been uploaded to the GPU, referenced through the RenderSystem's ResourceRegistry.
+#[derive(Clone, Debug)]
+pub struct Mesh {
+    /// The name of the mesh.
+    pub name      : String,
+    /// The handle of the mesh's vertex buffer.
+    pub vertices  : Handle<Rc<VertexBuffer>>,
+    /// The handle of the mesh's index buffer.
+    pub indices   : Handle<Rc<IndexBuffer>>,
+    /// The number of indices to draw.
+    pub n_indices : u32,
+}
+
+impl Mesh {
+    /// Uploads the given MeshData to the GPU and registers its buffers in the ResourceRegistry.
+    /// 
+    /// # Arguments
+    /// - `path`: The path the mesh was loaded from (used for debugging).
+    /// - `data`: The MeshData to upload.
+    /// - `device`: The Device where the new Buffers will be allocated.
+    /// - `memory_pool`: The MemoryPool where to allocate the memory for the buffers (and temporary staging buffers).
+    /// - `command_pool`: The CommandPool where we will get a command buffer to do the copies on.
+    /// - `resources`: The ResourceRegistry that will own the buffers.
+    /// 
+    /// # Returns
+    /// A new Mesh with handles to the uploaded buffers.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to allocate or populate the buffers.
+    pub fn upload(path: &Path, data: &MeshData, device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>, resources: &mut ResourceRegistry) -> Result<Self, Error> {
+        // Create and populate the buffers
+        let vertices: Rc<VertexBuffer> = match create_vertex_buffer("mesh", device, memory_pool, command_pool, &data.vertices) {
+            Ok(vertices) => vertices,
+            Err(err)     => { return Err(Error::MeshUploadError{ path: path.to_path_buf(), err }); }
+        };
+        let indices: Rc<IndexBuffer> = match create_index_buffer("mesh", device, memory_pool, command_pool, &data.indices) {
+            Ok(indices) => indices,
+            Err(err)    => { return Err(Error::MeshUploadError{ path: path.to_path_buf(), err }); }
+        };
+
+        // Register them
+        Ok(Self {
+            name      : data.name.clone(),
+            vertices  : resources.vertex_buffers.insert(vertices),
+            indices   : resources.index_buffers.insert(indices),
+            n_indices : data.indices.len() as u32,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+//  TEXTURE.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:46:18
+//  Last edited:
+//    17 Oct 2026, 04:46:18
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements loading textures from image files.
+// 
+
+use std::path::Path;
+
+use image::RgbaImage;
+
+pub use crate::errors::AssetError as Error;
+
+
+/***** LIBRARY *****/
+/// A texture loaded from disk, decoded to 8-bit RGBA pixels.
+#[derive(Clone, Debug)]
+pub struct Texture {
+    /// The width of the texture, in pixels.
+    pub width  : u32,
+    /// The height of the texture, in pixels.
+    pub height : u32,
+    /// The pixels of the texture, as row-major RGBA8 values (so `width * height * 4` bytes).
+    pub pixels : Vec<u8>,
+}
+
+impl Texture {
+    /// Loads a texture from the given image file. Supports PNG and JPEG.
+    /// 
+    /// # Arguments
+    /// - `path`: The path of the image file.
+    /// 
+    /// # Returns
+    /// A new Texture with the decoded pixels.
+    /// 
+    /// # Errors
+    /// This function errors if the file could not be read or decoded.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path: &Path = path.as_ref();
+
+        // Decode the image and convert it to RGBA
+        let image: RgbaImage = match image::open(path) {
+            Ok(image) => image.into_rgba8(),
+            Err(err)  => { return Err(Error::ImageLoadError{ path: path.to_path_buf(), err }); }
+        };
+
+        // Done
+        Ok(Self {
+            width  : image.width(),
+            height : image.height(),
+            pixels : image.into_raw(),
+        })
+    }
+}
@@ -0,0 +1,67 @@
+//  VERTEX.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:46:18
+//  Last edited:
+//    17 Oct 2026, 04:46:18
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the Vertex layout of meshes loaded from disk.
+// 
+
+use memoffset::offset_of;
+
+use rust_vk::auxillary::enums::AttributeLayout;
+use rust_vk::auxillary::structs::VertexAttribute;
+use rust_vk::pools::memory::spec::Vertex;
+
+
+/***** LIBRARY *****/
+/// The Vertex of meshes loaded by the AssetManager.
+#[repr(C)]
+#[derive(Clone, Debug, Default)]
+pub struct MeshVertex {
+    /// The coordinate of the vertex (in model space)
+    pub pos    : [f32; 3],
+    /// The normal of the vertex (zero if the file does not define any)
+    pub normal : [f32; 3],
+    /// The texture coordinate of the vertex (zero if the file does not define any)
+    pub uv     : [f32; 2],
+}
+
+impl Vertex for MeshVertex {
+    /// Returns the descriptions that list the attributes (=fields) for this Vertex.
+    /// 
+    /// # Returns
+    /// A list of VertexAttributeDescription that describes the attributes for this Vertex.
+    #[inline]
+    fn vk_attributes() -> Vec<VertexAttribute> {
+        vec![
+            VertexAttribute {
+                binding  : 0,
+                location : 0,
+                layout   : AttributeLayout::Float3,
+                offset   : offset_of!(MeshVertex, pos),
+            },
+            VertexAttribute {
+                binding  : 0,
+                location : 1,
+                layout   : AttributeLayout::Float3,
+                offset   : offset_of!(MeshVertex, normal),
+            },
+            VertexAttribute {
+                binding  : 0,
+                location : 2,
+                layout   : AttributeLayout::Float2,
+                offset   : offset_of!(MeshVertex, uv),
+            },
+        ]
+    }
+
+    /// Returns the size (in bytes) of each Vertex.
+    #[inline]
+    fn vk_size() -> usize { std::mem::size_of::<Self>() }
+}
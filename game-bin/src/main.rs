@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 12:11:47
 //  Last edited:
-//    07 Aug 2022, 18:35:48
+//    17 Oct 2026, 05:48:35
 //  Auto updated?
 //    Yes
 // 
@@ -13,27 +13,107 @@
 // 
 
 use std::fs::File;
+use std::time::Duration;
 
 use log::{error, info, LevelFilter};
 use simplelog::{ColorChoice, CombinedLogger, TerminalMode, TermLogger, WriteLogger};
+use winit::event::VirtualKeyCode;
 
 use game_cfg::Config;
+use game_cfg::spec::{Budgets, GpuSelector as ConfigGpuSelector, Quality};
+use game_cfg::paths::ensure_dir;
 use rust_ecs::Ecs;
 use rust_win::spec::WindowInfo;
-use game_evt::EventSystem;
+use game_evt::{EventSystem, MessageBus, Time};
+use game_evt::spec::{BudgetsChanged, InputEvent};
 use game_gfx::RenderSystem;
-use game_gfx::spec::{AppInfo, VulkanInfo};
+use game_gfx::spec::{AppInfo, GpuSelector, PipelineId, RenderBudgets, VulkanInfo, WindowId};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Maps the number keys to the pipelines of the demo mode.
+/// 
+/// # Arguments
+/// - `key`: The key that was pressed.
+/// 
+/// # Returns
+/// The PipelineId of the pipeline to switch to, or None if the key isn't bound.
+fn demo_pipeline(key: VirtualKeyCode) -> Option<PipelineId> {
+    match key {
+        VirtualKeyCode::Key1 => Some(PipelineId::Triangle),
+        VirtualKeyCode::Key2 => Some(PipelineId::Square),
+        VirtualKeyCode::Key3 => Some(PipelineId::TestPattern),
+        _                    => None,
+    }
+}
+
+/// Maps the function keys to the quality presets.
+/// 
+/// # Arguments
+/// - `key`: The key that was pressed.
+/// 
+/// # Returns
+/// The Quality to switch to, or None if the key isn't bound.
+fn quality_key(key: VirtualKeyCode) -> Option<Quality> {
+    match key {
+        VirtualKeyCode::F1 => Some(Quality::Low),
+        VirtualKeyCode::F2 => Some(Quality::Medium),
+        VirtualKeyCode::F3 => Some(Quality::High),
+        VirtualKeyCode::F4 => Some(Quality::Ultra),
+        _                  => None,
+    }
+}
+
+/// Converts the budgets from the Config to the ones the RenderSystem understands.
+/// 
+/// # Arguments
+/// - `budgets`: The Budgets from the Config.
+/// 
+/// # Returns
+/// The RenderBudgets with the budgets that apply to rendering.
+fn render_budgets(budgets: &Budgets) -> RenderBudgets {
+    RenderBudgets {
+        texture_vram_mb   : budgets.texture_vram_mb,
+        shadow_resolution : budgets.shadow_resolution,
+        draw_distance     : budgets.draw_distance,
+    }
+}
+
+/// Converts the GPU selection from the Config to the one the RenderSystem understands.
+/// 
+/// # Arguments
+/// - `gpu`: The GpuSelector from the Config.
+/// 
+/// # Returns
+/// The equivalent GpuSelector for the VulkanInfo.
+fn gpu_selector(gpu: &ConfigGpuSelector) -> GpuSelector {
+    match gpu {
+        ConfigGpuSelector::Index(index) => GpuSelector::Index(*index),
+        ConfigGpuSelector::Name(name)   => GpuSelector::Name(name.clone()),
+    }
+}
+
+
+
 
 
 /***** ENTRYPOINT *****/
 fn main() {
     // Load the config
-    let config = match Config::new() {
+    let mut config = match Config::new() {
         Ok(config) => config,
         Err(err)   => { eprintln!("Could not load configuration: {}", err); std::process::exit(1); }
     };
+    if config.dump_config {
+        println!("{}", config.dump());
+        std::process::exit(0);
+    }
 
     // Initialize the logger
+    if let Err(err) = ensure_dir("log", &config.dirs.logs) {
+        eprintln!("Could not prepare log directory: {}", err);
+        std::process::exit(1);
+    }
     if let Err(err) = CombinedLogger::init(vec![
          TermLogger::new(config.verbosity, Default::default(), TerminalMode::Mixed, ColorChoice::Auto),
          WriteLogger::new(LevelFilter::Debug, Default::default(), File::create(&config.files.log).unwrap_or_else(|err| panic!("Could not open log file '{}': {}", config.files.log.display(), err))),
@@ -49,10 +129,10 @@ fn main() {
     // Initialize the entity component system
     let ecs = Ecs::new(2048);
     // Initialize the event system
-    let event_system = EventSystem::new(ecs.clone());
+    let mut event_system = EventSystem::new(ecs.clone());
 
     // Initialize the render system
-    let render_system = match RenderSystem::new(
+    let mut render_system = match RenderSystem::new(
         ecs.clone(),
         event_system.event_loop(),
         AppInfo::new(
@@ -63,10 +143,10 @@ fn main() {
         ),
         WindowInfo::new(
             "Game-Rust",
-            config.window_mode,
+            config.window_mode.clone(),
         ),
         VulkanInfo {
-            gpu   : config.gpu,
+            gpu   : gpu_selector(&config.gpu),
             debug : config.verbosity >= LevelFilter::Debug,
         },
     ) {
@@ -74,6 +154,92 @@ fn main() {
         Err(err)   => { error!("Could not initialize render system: {}", err); std::process::exit(1); }
     };
 
+    // Remember the GPU we fell back to, if the user asked for it
+    if config.auto_fix_config && render_system.is_gpu_fallback() {
+        match config.persist_gpu(render_system.gpu_name()) {
+            Ok(_)    => { info!("Updated the settings file to use GPU '{}'", render_system.gpu_name()); },
+            Err(err) => { error!("Could not update the GPU in the settings file: {}", err); },
+        }
+    }
+
+    // Swap to the test pattern if the user asked for it
+    if config.test_pattern {
+        info!("Rendering test pattern instead of the game");
+        if let Err(err) = render_system.set_pipeline(WindowId::Main, PipelineId::TestPattern) {
+            error!("Could not switch to the test pattern: {}", err);
+            std::process::exit(1);
+        }
+    }
+
+    // Run the fixed updates at the configured rate
+    info!("Running fixed updates at {} Hz", config.tick_rate);
+    event_system.time().borrow_mut().set_fixed_step(Duration::from_secs(1) / config.tick_rate);
+
+    // Become idle after some time in the background, unless the user disabled it
+    event_system.time().borrow_mut().idle_mut().set_timeout(config.idle_timeout);
+
+    // Let the debug keys control the simulation time, if the user asked for it
+    if config.time_controls {
+        info!("Time controls enabled; press 'P' to pause, '.' to step and '-'/'=' to change the time scale");
+        event_system.time().borrow_mut().set_debug_controls(true);
+    }
+
+    // Log the frame statistics, if the user asked for it
+    if config.show_fps {
+        event_system.time().borrow_mut().stats_mut().set_reporting(true);
+    }
+
+    // Rebuild pipelines whose shaders change, if the user asked for it
+    if config.hot_reload_shaders {
+        render_system.watch_shaders();
+        event_system.on_tick("shader_reload", |_: &Time, _: &MessageBus, render_system: &mut RenderSystem| render_system.reload_shaders());
+    }
+
+    // In demo mode, let the number keys switch between the pipelines
+    if config.demo {
+        info!("Demo mode enabled; press 1, 2 or 3 to switch between the Triangle, Square and TestPattern pipelines");
+        let mut current: PipelineId = if config.test_pattern { PipelineId::TestPattern } else { PipelineId::Square };
+        event_system.on_tick("demo", move |_: &Time, bus: &MessageBus, render_system: &mut RenderSystem| {
+            for event in bus.read::<InputEvent>() {
+                let pipeline: PipelineId = match event {
+                    InputEvent::KeyPressed(_, key) => match demo_pipeline(*key) {
+                        Some(pipeline) => pipeline,
+                        None           => { continue; }
+                    },
+                    _ => { continue; }
+                };
+                if pipeline == current { continue; }
+
+                // Swap the pipeline of the main window
+                match render_system.set_pipeline(WindowId::Main, pipeline) {
+                    Ok(_)    => { info!("Switched to the {} pipeline", pipeline); current = pipeline; },
+                    Err(err) => { error!("Could not switch to the {} pipeline: {}", pipeline, err); },
+                }
+            }
+        });
+    }
+
+    // Apply the budgets of the quality preset, and let the function keys switch between the presets at runtime
+    render_system.set_budgets(render_budgets(&config.budgets));
+    info!("Quality preset is '{}'; press F1, F2, F3 or F4 to switch to the low, medium, high or ultra preset", config.quality);
+    event_system.on_tick("quality", move |_: &Time, bus: &MessageBus, _: &mut RenderSystem| {
+        for event in bus.read::<InputEvent>() {
+            let quality: Quality = match event {
+                InputEvent::KeyPressed(_, key) => match quality_key(*key) {
+                    Some(quality) => quality,
+                    None          => { continue; }
+                },
+                _ => { continue; }
+            };
+            if quality == config.quality { continue; }
+
+            // Recompute the budgets, and let the systems know
+            let budgets: &Budgets = config.set_quality(quality);
+            info!("Switched to the '{}' quality preset", quality);
+            bus.publish(BudgetsChanged{ render: render_budgets(budgets), max_particles: budgets.max_particles });
+        }
+    });
+
 
 
     // Enter the main loop
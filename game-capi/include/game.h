@@ -0,0 +1,49 @@
+/* GAME.h
+ *   by Lut99
+ *
+ * Created:
+ *   17 Oct 2026, 04:48:34
+ * Last edited:
+ *   17 Oct 2026, 04:48:34
+ * Auto updated?
+ *   Yes
+ *
+ * Description:
+ *   C declarations for the game-capi library, which embeds the engine in
+ *   non-Rust hosts. Engines must be created, pumped and destroyed on the
+ *   process' main thread (a requirement of the underlying windowing
+ *   library).
+**/
+
+#ifndef GAME_H
+#define GAME_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Opaque handle to an engine instance. */
+typedef struct GameEngine GameEngine;
+
+/* Receives log messages. level: 1 = error, 2 = warning, 3 = info, 4 = debug, 5 = trace. */
+typedef void (*GameLogCallback)(int level, const char* message, void* user);
+
+/* Registers (or, with NULL, removes) the log callback. Returns 0 on success, -1 on failure. */
+int game_set_log_callback(GameLogCallback callback, void* user);
+/* Returns the last error on the calling thread, or NULL if there was none. */
+const char* game_last_error(void);
+
+/* Creates an engine with a window of the given size. Returns NULL on failure. */
+GameEngine* game_engine_create(const char* title, uint32_t width, uint32_t height, uint32_t gpu, int debug);
+/* Processes events and renders one frame. Returns 1 while running, 0 once stopped, -1 on error (see game_last_error()). */
+int game_engine_pump(GameEngine* engine);
+/* Waits for the GPU and destroys the engine. Accepts NULL. */
+void game_engine_destroy(GameEngine* engine);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,296 @@
+//  LIB.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:48:34
+//  Last edited:
+//    17 Oct 2026, 05:47:30
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Exposes a minimal C API for embedding the engine in non-Rust hosts.
+//!   See `include/game.h` for the C declarations.
+// 
+
+use std::cell::RefCell;
+use std::ffi::{c_void, CStr, CString};
+use std::fmt::Display;
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use rust_ecs::Ecs;
+use rust_win::spec::{WindowInfo, WindowMode};
+
+use game_evt::EventSystem;
+use game_gfx::RenderSystem;
+use game_gfx::spec::{AppInfo, GpuSelector, VulkanInfo};
+
+
+/***** TYPES *****/
+/// The signature of the callback that receives log messages.
+/// 
+/// No engine locks are held while it runs, so it may log or call `game_set_log_callback()` itself.
+/// 
+/// # Arguments
+/// - `level`: The level of the message (1 = error, 2 = warning, 3 = info, 4 = debug, 5 = trace).
+/// - `message`: The message, as a NULL-terminated UTF-8 string. Only valid for the duration of the call.
+/// - `user`: The user pointer given to `game_set_log_callback()`.
+pub type GameLogCallback = extern "C" fn(level: c_int, message: *const c_char, user: *mut c_void);
+
+/// Wraps the user pointer of the log callback so it may be stored in a static.
+#[derive(Clone, Copy)]
+struct UserData(*mut c_void);
+// The pointer is only ever handed back to the host, which is responsible for its thread-safety
+unsafe impl Send for UserData {}
+
+
+
+/// The engine as handed out to C hosts.
+pub struct GameEngine {
+    /// The RenderSystem that renders to the engine's window. Dropped first, as it refers to the EventSystem's EventLoop.
+    render_system : RenderSystem,
+    /// The EventSystem that processes the window's events.
+    event_system  : EventSystem,
+    /// The Entity Component System shared by the systems.
+    _ecs          : Rc<RefCell<Ecs>>,
+}
+
+
+
+
+
+/***** GLOBALS *****/
+/// The log callback registered by the host, if any.
+static LOG_CALLBACK: Mutex<Option<(GameLogCallback, UserData)>> = Mutex::new(None);
+
+thread_local! {
+    /// The last error that occurred on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Stores the given message as the last error of this thread.
+fn set_last_error<S: Into<String>>(message: S) {
+    let message: String = message.into().replace('\0', "\\0");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(CString::new(message).unwrap()));
+}
+
+/// Converts the result of `EventSystem::pump()` to the status code of `game_engine_pump()`, setting the last error if it failed.
+fn pump_status<E: Display>(res: Result<bool, E>) -> c_int {
+    match res {
+        Ok(true)  => 1,
+        Ok(false) => 0,
+        Err(err)  => {
+            set_last_error(format!("Could not pump engine: {}", err));
+            -1
+        },
+    }
+}
+
+/// Runs the given closure, turning any panic into an error instead of unwinding into the host.
+fn guard<T>(default: T, f: impl FnOnce() -> T) -> T {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(res) => res,
+        Err(err) => {
+            let message: &str = err.downcast_ref::<&str>().copied().or_else(|| err.downcast_ref::<String>().map(|s| s.as_str())).unwrap_or("unknown panic");
+            set_last_error(format!("Engine panicked: {}", message));
+            default
+        },
+    }
+}
+
+
+
+
+
+/***** LOGGER *****/
+/// A `log` Logger that forwards every message to the host's callback.
+struct CallbackLogger;
+
+impl Log for CallbackLogger {
+    #[inline]
+    fn enabled(&self, _metadata: &Metadata) -> bool { true }
+
+    fn log(&self, record: &Record) {
+        // Copy the callback out, so the lock is released before we call it (the host may log or replace the callback from within)
+        let callback: Option<(GameLogCallback, UserData)> = match LOG_CALLBACK.lock() {
+            Ok(callback) => callback.as_ref().map(|(callback, user)| (*callback, *user)),
+            Err(_)       => { return; }
+        };
+        if let Some((callback, user)) = callback {
+            let level: c_int = match record.level() {
+                Level::Error => 1,
+                Level::Warn  => 2,
+                Level::Info  => 3,
+                Level::Debug => 4,
+                Level::Trace => 5,
+            };
+            let message: CString = CString::new(format!("{}", record.args()).replace('\0', "\\0")).unwrap();
+            callback(level, message.as_ptr(), user.0);
+        }
+    }
+
+    #[inline]
+    fn flush(&self) {}
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Registers the callback that receives the engine's log messages. Pass NULL to stop receiving them.
+/// 
+/// # Returns
+/// 0 on success, or -1 if another logger was already installed in this process (see `game_last_error()`).
+#[no_mangle]
+pub extern "C" fn game_set_log_callback(callback: Option<GameLogCallback>, user: *mut c_void) -> c_int {
+    let user: UserData = UserData(user);
+    guard(-1, move || {
+        // Install our logger the first time a callback is given
+        static INSTALLED: Mutex<bool> = Mutex::new(false);
+        let mut installed = INSTALLED.lock().unwrap_or_else(|err| err.into_inner());
+        if !*installed && callback.is_some() {
+            if let Err(err) = log::set_boxed_logger(Box::new(CallbackLogger)) {
+                set_last_error(format!("Could not install logger: {}", err));
+                return -1;
+            }
+            log::set_max_level(LevelFilter::Trace);
+            *installed = true;
+        }
+
+        // Store the callback
+        *LOG_CALLBACK.lock().unwrap_or_else(|err| err.into_inner()) = callback.map(|callback| (callback, user));
+        0
+    })
+}
+
+/// Returns the last error that occurred on the calling thread, or NULL if there was none.
+/// 
+/// The string remains valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn game_last_error() -> *const c_char {
+    guard(ptr::null(), || LAST_ERROR.with(|last| last.borrow().as_ref().map(|err| err.as_ptr()).unwrap_or(ptr::null())))
+}
+
+
+
+/// Creates a new engine with a window of the given size.
+/// 
+/// # Arguments
+/// - `title`: The title of the window, as a NULL-terminated UTF-8 string.
+/// - `width`: The width of the window, in pixels.
+/// - `height`: The height of the window, in pixels.
+/// - `gpu`: The index of the GPU to render with.
+/// - `debug`: If non-zero, enables the Vulkan validation layers.
+/// 
+/// # Returns
+/// A new engine that must be freed with `game_engine_destroy()`, or NULL if creation failed (see `game_last_error()`).
+/// 
+/// # Safety
+/// `title` must be NULL or point to a NULL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn game_engine_create(title: *const c_char, width: u32, height: u32, gpu: u32, debug: c_int) -> *mut GameEngine {
+    if title.is_null() { set_last_error("Given title is NULL"); return ptr::null_mut(); }
+    let title: String = match CStr::from_ptr(title).to_str() {
+        Ok(title) => title.into(),
+        Err(err)  => { set_last_error(format!("Given title is not valid UTF-8: {}", err)); return ptr::null_mut(); }
+    };
+
+    guard(ptr::null_mut(), move || {
+        // Initialize the systems like the game binary does
+        let ecs = Ecs::new(2048);
+        let event_system = EventSystem::new(ecs.clone());
+        let render_system = match RenderSystem::new(
+            ecs.clone(),
+            event_system.event_loop(),
+            AppInfo::new(
+                title.clone(),
+                env!("CARGO_PKG_VERSION"),
+                EventSystem::name(),
+                EventSystem::version(),
+            ),
+            WindowInfo::new(
+                title,
+                WindowMode::Windowed{ resolution: (width, height) },
+            ),
+            VulkanInfo {
+                gpu   : GpuSelector::Index(gpu as usize),
+                debug : debug != 0,
+            },
+        ) {
+            Ok(system) => system,
+            Err(err)   => { set_last_error(format!("Could not initialize render system: {}", err)); return ptr::null_mut(); }
+        };
+
+        // Hand it to the host
+        Box::into_raw(Box::new(GameEngine {
+            render_system,
+            event_system,
+            _ecs : ecs,
+        }))
+    })
+}
+
+/// Processes pending events and renders a single frame.
+/// 
+/// # Returns
+/// 1 if the engine is still running, 0 if it stopped (e.g., because the window was closed) or -1 if an error occurred (see `game_last_error()`). Once this returns anything but 1, the engine should be destroyed.
+/// 
+/// # Safety
+/// `engine` must be NULL or an engine returned by `game_engine_create()` that has not been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn game_engine_pump(engine: *mut GameEngine) -> c_int {
+    let engine: &mut GameEngine = match engine.as_mut() {
+        Some(engine) => engine,
+        None         => { set_last_error("Given engine is NULL"); return -1; }
+    };
+    guard(-1, || pump_status(engine.event_system.pump(&mut engine.render_system)))
+}
+
+/// Destroys the given engine, waiting for the GPU to finish first. Does nothing if `engine` is NULL.
+/// 
+/// # Safety
+/// `engine` must be NULL or an engine returned by `game_engine_create()` that has not been destroyed yet.
+#[no_mangle]
+pub unsafe extern "C" fn game_engine_destroy(engine: *mut GameEngine) {
+    if engine.is_null() { return; }
+    let engine: Box<GameEngine> = Box::from_raw(engine);
+    guard((), move || {
+        if let Err(err) = engine.render_system.wait_for_idle() { set_last_error(format!("Could not wait for the device to become idle: {}", err)); }
+        drop(engine);
+    })
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_pump_status() {
+        assert_eq!(pump_status::<game_evt::Error>(Ok(true)), 1);
+        assert_eq!(pump_status::<game_evt::Error>(Ok(false)), 0);
+        assert!(game_last_error().is_null());
+
+        // Errors are reported through the last error
+        let err = game_evt::Error::IdleError{ err: game_gfx::Error::NoSupportedGpu };
+        assert_eq!(pump_status(Err(err)), -1);
+        let last: &CStr = unsafe { CStr::from_ptr(game_last_error()) };
+        assert!(last.to_str().unwrap().starts_with("Could not pump engine: "));
+    }
+}
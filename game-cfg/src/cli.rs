@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 10:55:40
 //  Last edited:
-//    06 Aug 2022, 17:47:10
+//    17 Oct 2026, 05:46:29
 //  Auto updated?
 //    Yes
 // 
@@ -12,39 +12,105 @@
 //!   Contains the part of the config that parses the command-line
 // 
 
+use std::path::PathBuf;
+
 use clap::Parser;
 use log::LevelFilter;
 
 use rust_win::spec::WindowMode;
 
-use crate::spec::Resolution;
+use crate::spec::{GpuSelector, Quality, Resolution};
 
 
 /***** ARGUMENT STRUCTS *****/
 /// Defines the command-line part of the Config struct.
+/// 
+/// Every field of the settings file can be overridden here, either by a flag or by the matching `GAME_RUST_*` environment variable. The precedence is: CLI > environment variable > settings file > default.
 #[derive(Debug, Parser)]
 #[clap(author, version, about, long_about = None)]
 pub(crate) struct Arguments {
     /// The verbosity of the logging (the CLI-part, at least)
-    #[clap(short, long, help = "The verbosity of the terminal logging.")]
+    #[clap(short, long, env = "GAME_RUST_VERBOSITY", help = "The verbosity of the terminal logging.")]
     pub(crate) verbosity : Option<LevelFilter>,
 
+    /// If given, overrides the config directory
+    #[clap(long, help = "The directory with the settings file. Overrides the GAME_RUST_CONFIG_DIR environment variable and the platform default.")]
+    pub(crate) config_dir : Option<PathBuf>,
+    /// If given, overrides the save directory
+    #[clap(long, help = "The directory with the save games. Overrides the GAME_RUST_SAVE_DIR environment variable and the platform default.")]
+    pub(crate) save_dir   : Option<PathBuf>,
+    /// If given, overrides the cache directory
+    #[clap(long, help = "The directory with cached files. Overrides the GAME_RUST_CACHE_DIR environment variable and the platform default.")]
+    pub(crate) cache_dir  : Option<PathBuf>,
+    /// If given, overrides the log directory
+    #[clap(long, help = "The directory with the log files. Overrides the GAME_RUST_LOG_DIR environment variable and the platform default.")]
+    pub(crate) log_dir    : Option<PathBuf>,
+    /// If given, overrides the asset directory
+    #[clap(long, help = "The directory with the game's assets. Overrides the GAME_RUST_ASSET_DIR environment variable and the default 'assets' folder next to the executable.")]
+    pub(crate) asset_dir  : Option<PathBuf>,
+
     /// If given, overrides the GPU to use
-    #[clap(short, long, help = "The GPU to use during the rendering process.")]
-    pub(crate) gpu          : Option<usize>,
+    #[clap(short, long, env = "GAME_RUST_GPU", help = "The GPU to use during the rendering process. Either its index or (part of) its name, e.g. 'nvidia'. See the 'game-list' executable to discover the options.")]
+    pub(crate) gpu             : Option<GpuSelector>,
+    /// Whether to write a GPU fallback back to the settings file.
+    #[clap(long, help = "If the configured GPU is missing or unsupported and the game falls back to another one, writes that GPU to the settings file so the fallback sticks.")]
+    pub(crate) auto_fix_config : bool,
     /// The monitor where the window will be spawned.
-    #[clap(short, long, help = "The monitor where the window will be placed (as an index). Not relevant in 'windowed' window mode. See the 'game-list' executable to discover the options.")]
-    pub(crate) monitor      : Option<usize>,
+    #[clap(short, long, env = "GAME_RUST_MONITOR", help = "The monitor where the window will be placed (as an index). Not relevant in 'windowed' window mode. See the 'game-list' executable to discover the options.")]
+    pub(crate) monitor         : Option<usize>,
     /// The resolution of the window.
-    #[clap(short, long, help = "The resolution of the window. Should be in the form of '<width>x<height>', where '<width>' and '<height>' are unsigned integers. Not relevant in 'windowed_fullscreen' window mode. See the 'game-list' executable to discover the options.")]
-    pub(crate) resolution   : Option<Resolution>,
+    #[clap(short, long, env = "GAME_RUST_RESOLUTION", help = "The resolution of the window. Should be in the form of '<width>x<height>', where '<width>' and '<height>' are unsigned integers. Not relevant in 'windowed_fullscreen' window mode. See the 'game-list' executable to discover the options.")]
+    pub(crate) resolution      : Option<Resolution>,
     /// The refresh rate of the window.
-    #[clap(short='R', long, help = "The refresh rate of the window, in Hz. Only relevant in 'fullscreen' window mode. See the 'game-list' executable to discover the options.")]
-    pub(crate) refresh_rate : Option<u16>,
+    #[clap(short='R', long, env = "GAME_RUST_REFRESH_RATE", help = "The refresh rate of the window, in Hz. Only relevant in 'fullscreen' window mode. See the 'game-list' executable to discover the options.")]
+    pub(crate) refresh_rate    : Option<u16>,
     /// The refresh rate of the window.
-    #[clap(short, long, help = "The bit depth to render in (in bits-per-pixel). Only relevant in 'fullscreen' window mode. See the 'game-list' executable to discover the options.")]
-    pub(crate) bit_depth    : Option<usize>,
+    #[clap(short, long, env = "GAME_RUST_BIT_DEPTH", help = "The bit depth to render in (in bits-per-pixel). Only relevant in 'fullscreen' window mode. See the 'game-list' executable to discover the options.")]
+    pub(crate) bit_depth       : Option<usize>,
     /// The window mode to open the window in.
-    #[clap(short, long, help = "The window mode for the window. Can be 'windowed', 'windowed_fullscreen' or 'fullscreen'.")]
-    pub(crate) window_mode  : Option<WindowMode>,
+    #[clap(short, long, env = "GAME_RUST_WINDOW_MODE", help = "The window mode for the window. Can be 'windowed', 'windowed_fullscreen' or 'fullscreen'.")]
+    pub(crate) window_mode     : Option<WindowMode>,
+
+    /// The quality preset to use.
+    #[clap(short, long, env = "GAME_RUST_QUALITY", help = "The quality preset that determines the resource budgets. Can be 'low', 'medium', 'high' or 'ultra'.")]
+    pub(crate) quality      : Option<Quality>,
+    /// The number of fixed updates per second.
+    #[clap(long, env = "GAME_RUST_TICK_RATE", help = "The number of fixed updates (e.g., physics steps) per second, independent of the frame rate. Should be between 1 and 1000. Overrides the value in the settings file.")]
+    pub(crate) tick_rate    : Option<u32>,
+    /// The number of seconds in the background before the game is idle.
+    #[clap(long, env = "GAME_RUST_IDLE_TIMEOUT", help = "The number of seconds that none of the game's windows may have focus before the game is considered idle, after which it trims its GPU resources and renders at a low frame rate. Use 0 to never become idle. Overrides the value in the settings file.")]
+    pub(crate) idle_timeout : Option<u64>,
+
+    /// Overrides the texture VRAM budget.
+    #[clap(long, env = "GAME_RUST_TEXTURE_VRAM_MB", help = "The amount of VRAM that textures may use, in MiB. Overrides the quality preset.")]
+    pub(crate) texture_vram_mb   : Option<u32>,
+    /// Overrides the particle budget.
+    #[clap(long, env = "GAME_RUST_MAX_PARTICLES", help = "The maximum number of live particles. Overrides the quality preset.")]
+    pub(crate) max_particles     : Option<u32>,
+    /// Overrides the shadow map resolution.
+    #[clap(long, env = "GAME_RUST_SHADOW_RESOLUTION", help = "The width and height of shadow maps, in pixels. Overrides the quality preset.")]
+    pub(crate) shadow_resolution : Option<u32>,
+    /// Overrides the draw distance.
+    #[clap(long, env = "GAME_RUST_DRAW_DISTANCE", help = "The distance (in world units) beyond which objects are not drawn. Overrides the quality preset.")]
+    pub(crate) draw_distance     : Option<f32>,
+
+    /// Whether to print the merged configuration and quit.
+    #[clap(long, help = "If given, prints the directories and the settings that result from merging the CLI, the environment and the settings file, and then quits.")]
+    pub(crate) dump_config : bool,
+
+    /// Whether to render a test pattern instead of the game.
+    #[clap(long, help = "If given, renders a test pattern (colour bars, gradients and an alignment grid) instead of the game. Useful to validate monitor setup, scaling behaviour and colour correctness.")]
+    pub(crate) test_pattern       : bool,
+    /// Whether to let the number keys switch between the demo pipelines.
+    #[clap(long, help = "If given, the number keys switch the pipeline that renders the window at runtime: 1 for the triangle, 2 for the square and 3 for the test pattern.")]
+    pub(crate) demo               : bool,
+    /// Whether to rebuild pipelines when their compiled shaders change.
+    #[clap(long, help = "If given, pipelines are rebuilt whenever their compiled SPIR-V shaders change on disk (e.g., after running 'cargo build -p game-pip' or glslc). Only works in debug builds, which read the shaders from the source tree.")]
+    pub(crate) hot_reload_shaders : bool,
+    /// Whether to enable the debug keys that control the simulation time.
+    #[clap(long, help = "If given, enables the time debugging keys: 'P' pauses and resumes the simulation, '.' advances it by one step while paused, and '-'/'=' slow it down or speed it up (0.1x to 4x).")]
+    pub(crate) time_controls      : bool,
+    /// Whether to log frame statistics every second.
+    #[clap(long, help = "If given, logs the frames per second and the average, minimum and maximum frame time every second.")]
+    pub(crate) show_fps           : bool,
 }
@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 11:48:52
 //  Last edited:
-//    06 Aug 2022, 17:46:32
+//    17 Oct 2026, 05:53:12
 //  Auto updated?
 //    Yes
 // 
@@ -12,33 +12,63 @@
 //!   Contains the code that merges the settings file input with the
 // 
 
+use std::time::Duration;
+
 use clap::Parser;
 use log::LevelFilter;
 
 use rust_win::spec::WindowMode;
 
 use crate::errors::ConfigError as Error;
-use crate::spec::{DirConfig, FileConfig};
+use crate::paths::{ensure_dir, DirOverrides};
+use crate::spec::{BudgetOverrides, Budgets, DirConfig, FileConfig, GpuSelector, Quality};
 use crate::cli::Arguments;
-use crate::file::Settings;
+use crate::file::{MAX_TICK_RATE, MIN_TICK_RATE, Settings};
 
 
 /***** LIBRARY *****/
 /// The Config struct, which contains the configuration as loaded from both disk and CLI.
 #[derive(Debug)]
 pub struct Config {
-    /// The locations of the various directories. Is generated at runtime to resolve relative to the executable.
+    /// The locations of the various directories. Is resolved at runtime from the CLI, the environment and the platform.
     pub dirs  : DirConfig,
-    /// The locations of the various files. Is generated at runtime to resolve relative to the executable.
+    /// The locations of the various files. Is generated at runtime from the directories.
     pub files : FileConfig,
 
     /// The verbosity of the logging (the CLI-part, at least)
     pub verbosity : LevelFilter,
 
     /// The gpu to use during rendering
-    pub gpu         : usize,
+    pub gpu             : GpuSelector,
+    /// Whether a GPU fallback should be written back to the settings file
+    pub auto_fix_config : bool,
     /// The window mode
-    pub window_mode : WindowMode,
+    pub window_mode     : WindowMode,
+
+    /// The quality preset
+    pub quality          : Quality,
+    /// The resource budgets that follow from the quality preset and the overrides
+    pub budgets          : Budgets,
+    /// The overrides for individual budgets from the CLI, the environment and the settings file
+    pub budget_overrides : BudgetOverrides,
+
+    /// The number of fixed updates per second
+    pub tick_rate    : u32,
+    /// How long the game has to be in the background before it is idle, or None to never become idle
+    pub idle_timeout : Option<Duration>,
+
+    /// Whether to render a test pattern instead of the game
+    pub test_pattern       : bool,
+    /// Whether the number keys switch between the demo pipelines
+    pub demo               : bool,
+    /// Whether pipelines are rebuilt when their compiled shaders change
+    pub hot_reload_shaders : bool,
+    /// Whether the debug keys control the simulation time
+    pub time_controls      : bool,
+    /// Whether frame statistics are logged every second
+    pub show_fps           : bool,
+    /// Whether to print the merged configuration and quit
+    pub dump_config        : bool,
 }
 
 impl Config {
@@ -47,16 +77,33 @@ impl Config {
     /// **Returns**  
     /// A new Config on success, or else an Error.
     pub fn new() -> Result<Self, Error> {
-        // Generate the default paths
-        let dir_config  = DirConfig::new()?;
-        let file_config = FileConfig::new(&dir_config)?;
-
         // Load the CLI
         let args: Arguments = Arguments::parse();
-        // Load the settings file
-        let settings = match Settings::from_path(&file_config.settings) {
-            Ok(settings) => settings,
-            Err(err)     => { return Err(Error::SettingsLoadError{ err }); }  
+
+        // Resolve the paths, taking the CLI overrides into account
+        let dir_config  = DirConfig::new(DirOverrides {
+            config : args.config_dir.clone(),
+            saves  : args.save_dir.clone(),
+            cache  : args.cache_dir.clone(),
+            logs   : args.log_dir.clone(),
+            assets : args.asset_dir.clone(),
+        })?;
+        let file_config = FileConfig::new(&dir_config)?;
+
+        // Make sure there is a settings file to load, generating a default one on the first run (unless we're only dumping the config, which shouldn't touch the disk)
+        let settings = if args.dump_config && !file_config.settings.exists() {
+            Settings::default()
+        } else {
+            if !file_config.settings.exists() {
+                ensure_dir("config", &dir_config.config)?;
+                if let Err(err) = Settings::default().write(&file_config.settings) { return Err(Error::SettingsWriteError{ err }); }
+            }
+
+            // Load the settings file
+            match Settings::from_path(&file_config.settings) {
+                Ok(settings) => settings,
+                Err(err)     => { return Err(Error::SettingsLoadError{ err }); }  
+            }
         };
 
         // Throw stuff together in a window mode
@@ -93,6 +140,20 @@ impl Config {
         // Overwrite stuff if necessary
         let verbosity   = args.verbosity.unwrap_or(settings.verbosity);
         let gpu         = args.gpu.unwrap_or(settings.gpu);
+        let quality     = args.quality.unwrap_or(settings.quality);
+        let budget_overrides = BudgetOverrides {
+            texture_vram_mb   : args.texture_vram_mb.or(settings.budgets.texture_vram_mb),
+            max_particles     : args.max_particles.or(settings.budgets.max_particles),
+            shadow_resolution : args.shadow_resolution.or(settings.budgets.shadow_resolution),
+            draw_distance     : args.draw_distance.or(settings.budgets.draw_distance),
+        };
+        let budgets     = Budgets::with_overrides(quality, &budget_overrides);
+        let tick_rate   = args.tick_rate.unwrap_or(settings.tick_rate);
+        if !(MIN_TICK_RATE..=MAX_TICK_RATE).contains(&tick_rate) { return Err(Error::IllegalTickRate{ rate: tick_rate, min: MIN_TICK_RATE, max: MAX_TICK_RATE }); }
+        let idle_timeout = match args.idle_timeout.unwrap_or(settings.idle_timeout) {
+            0       => None,
+            timeout => Some(Duration::from_secs(timeout)),
+        };
 
         // Done, return
         Ok(Self {
@@ -102,7 +163,97 @@ impl Config {
             verbosity,
 
             gpu,
+            auto_fix_config : args.auto_fix_config,
             window_mode,
+
+            quality,
+            budgets,
+            budget_overrides,
+
+            tick_rate,
+            idle_timeout,
+
+            test_pattern       : args.test_pattern,
+            demo               : args.demo,
+            hot_reload_shaders : args.hot_reload_shaders,
+            time_controls      : args.time_controls,
+            show_fps           : args.show_fps,
+            dump_config        : args.dump_config,
         })
     }
+
+
+
+    /// Returns the settings that result from merging the CLI, the environment and the settings file, in the form of the settings file.
+    pub fn settings(&self) -> Settings {
+        Settings {
+            verbosity : self.verbosity,
+
+            gpu         : self.gpu.clone(),
+            window_mode : self.window_mode.clone(),
+
+            quality : self.quality,
+            budgets : self.budget_overrides.clone(),
+
+            tick_rate    : self.tick_rate,
+            idle_timeout : self.idle_timeout.map(|timeout| timeout.as_secs()).unwrap_or(0),
+        }
+    }
+
+    /// Describes the resolved directories and files and the merged settings, for `--dump-config`.
+    /// 
+    /// # Returns
+    /// A human-readable description, with the settings in the format of the settings file.
+    pub fn dump(&self) -> String {
+        let settings: String = match serde_json::to_string_pretty(&self.settings()) {
+            Ok(settings) => settings,
+            Err(err)     => format!("<could not serialize settings: {}>", err),
+        };
+        format!("Config directory : {}\nSave directory   : {}\nCache directory  : {}\nLog directory    : {}\nAsset directory  : {}\nSettings file    : {}\n\nSettings (CLI > environment > settings file > default):\n{}",
+            self.dirs.config.display(),
+            self.dirs.saves.display(),
+            self.dirs.cache.display(),
+            self.dirs.logs.display(),
+            self.dirs.assets.display(),
+            self.files.settings.display(),
+            settings,
+        )
+    }
+
+
+
+    /// Switches to another quality preset at runtime, recomputing the budgets (the overrides from the CLI, the environment and the settings file still apply).
+    /// 
+    /// Subsystems are not notified by this function; the game publishes the returned Budgets as a `BudgetsChanged` message on the EventSystem's MessageBus so they can re-apply them.
+    /// 
+    /// # Arguments
+    /// - `quality`: The new quality preset.
+    /// 
+    /// # Returns
+    /// The new budgets.
+    pub fn set_quality(&mut self, quality: Quality) -> &Budgets {
+        self.quality = quality;
+        self.budgets = Budgets::with_overrides(quality, &self.budget_overrides);
+        &self.budgets
+    }
+
+    /// Writes the given GPU to the settings file (e.g., after falling back to another GPU), and uses it from now on.
+    /// 
+    /// The GPU is stored by name, so the setting keeps pointing to the same GPU if the order of the GPUs changes. Only the GPU is changed in the file; other settings that were overridden on the command line are not persisted.
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the GPU to persist.
+    /// 
+    /// # Errors
+    /// This function errors if the settings file could not be read or written.
+    pub fn persist_gpu<S: Into<String>>(&mut self, name: S) -> Result<(), Error> {
+        let mut settings = match Settings::from_path(&self.files.settings) {
+            Ok(settings) => settings,
+            Err(err)     => { return Err(Error::SettingsLoadError{ err }); }
+        };
+        settings.gpu = GpuSelector::Name(name.into());
+        if let Err(err) = settings.write(&self.files.settings) { return Err(Error::SettingsWriteError{ err }); }
+        self.gpu = settings.gpu;
+        Ok(())
+    }
 }
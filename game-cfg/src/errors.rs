@@ -4,7 +4,7 @@
  * Created:
  *   26 Mar 2022, 11:12:24
  * Last edited:
- *   11 Jul 2022, 19:11:08
+ *   17 Oct 2026, 05:46:29
  * Auto updated?
  *   Yes
  *
@@ -28,6 +28,10 @@ pub enum SettingsError {
 
     /// Could not parse a WindowMode.
     UnknownWindowMode{ raw: String },
+    /// Could not parse a Quality.
+    UnknownQuality{ raw: String },
+    /// The given GPU name was empty.
+    EmptyGpuName,
 
     /// Could not open the Settings file.
     OpenError{ path: PathBuf, err: std::io::Error },
@@ -49,6 +53,8 @@ impl Display for SettingsError {
             
 
             UnknownWindowMode{ raw } => write!(f, "Unknown window mode '{}'", raw),
+            UnknownQuality{ raw }    => write!(f, "Unknown quality preset '{}' (expected 'low', 'medium', 'high' or 'ultra')", raw),
+            EmptyGpuName             => write!(f, "GPU name cannot be empty"),
 
             OpenError{ path, err }  => write!(f, "Could not open settings file '{}': {}", path.display(), err),
             ParseError{ path, err } => write!(f, "Could not parse settings file '{}': {}", path.display(), err),
@@ -75,8 +81,20 @@ pub enum ConfigError {
     /// The given relative path tried to escape the parent path
     RelativeEscape{ base: PathBuf, path: PathBuf },
 
+    /// The platform does not define a directory we need
+    NoPlatformDir{ what: &'static str },
+    /// The given directory path exists but is not a directory
+    NotADirectory{ what: &'static str, path: PathBuf },
+    /// Could not create the given directory
+    DirCreateError{ what: &'static str, path: PathBuf, err: std::io::Error },
+
     /// Could not load the settings file.
     SettingsLoadError{ err: SettingsError },
+    /// Could not write the settings file.
+    SettingsWriteError{ err: SettingsError },
+
+    /// The tick rate is outside of the range we support.
+    IllegalTickRate{ rate: u32, min: u32, max: u32 },
 }
 
 impl Display for ConfigError {
@@ -88,7 +106,14 @@ impl Display for ConfigError {
             PathToStringError{ path }    => write!(f, "Could not convert '{}' to a string", path.display()),
             RelativeEscape{ base, path } => write!(f, "Given path '{}' tries to escape base path '{}': use absolute paths instead", path.display(), base.display()),
 
-            SettingsLoadError{ err } => write!(f, "Could not load the settings file: {}", err),
+            NoPlatformDir{ what }             => write!(f, "Could not find the {} directory for this platform: use the CLI or environment variable to give one manually", what),
+            NotADirectory{ what, path }       => write!(f, "Path '{}' for the {} directory exists but is not a directory", path.display(), what),
+            DirCreateError{ what, path, err } => write!(f, "Could not create {} directory '{}': {}", what, path.display(), err),
+
+            SettingsLoadError{ err }  => write!(f, "Could not load the settings file: {}", err),
+            SettingsWriteError{ err } => write!(f, "Could not write the settings file: {}", err),
+
+            IllegalTickRate{ rate, min, max } => write!(f, "Tick rate {} is out of range: it should be between {} and {} fixed updates per second", rate, min, max),
         }
     }
 }
@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 11:04:45
 //  Last edited:
-//    06 Aug 2022, 17:48:05
+//    17 Oct 2026, 05:46:29
 //  Auto updated?
 //    Yes
 // 
@@ -19,9 +19,36 @@ use log::LevelFilter;
 use rust_win::spec::WindowMode;
 use serde::{Deserialize, Serialize};
 
+use crate::spec::{BudgetOverrides, GpuSelector, Quality};
+
 pub use crate::errors::SettingsError as Error;
 
 
+/***** CONSTANTS *****/
+/// The number of fixed updates per second if the settings file does not specify it.
+pub const DEFAULT_TICK_RATE: u32 = 60;
+/// The lowest number of fixed updates per second we accept.
+pub const MIN_TICK_RATE: u32 = 1;
+/// The highest number of fixed updates per second we accept.
+pub const MAX_TICK_RATE: u32 = 1000;
+/// The number of seconds in the background before the game is idle, if the settings file does not specify it.
+pub const DEFAULT_IDLE_TIMEOUT: u64 = 30;
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the default tick rate, for serde.
+#[inline]
+fn default_tick_rate() -> u32 { DEFAULT_TICK_RATE }
+
+/// Returns the default idle timeout, for serde.
+#[inline]
+fn default_idle_timeout() -> u64 { DEFAULT_IDLE_TIMEOUT }
+
+
+
 /***** SETTINGS STRUCT *****/
 /// Defines the settings to load, and how to load them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,10 +56,42 @@ pub struct Settings {
     /// The debug-level
     pub verbosity : LevelFilter,
 
-    /// The GPU to use
-    pub gpu         : usize,
+    /// The GPU to use, by index or by name
+    pub gpu         : GpuSelector,
     /// The WindowMode for the window.
     pub window_mode : WindowMode,
+
+    /// The quality preset that determines the resource budgets.
+    #[serde(default)]
+    pub quality : Quality,
+    /// Overrides for individual resource budgets.
+    #[serde(default)]
+    pub budgets : BudgetOverrides,
+
+    /// The number of fixed updates per second.
+    #[serde(default = "default_tick_rate")]
+    pub tick_rate    : u32,
+    /// The number of seconds in the background before the game is idle and trims its resources (0 to never become idle).
+    #[serde(default = "default_idle_timeout")]
+    pub idle_timeout : u64,
+}
+
+impl Default for Settings {
+    /// The settings that are written on the first run, when there is no settings file yet.
+    fn default() -> Self {
+        Self {
+            verbosity : LevelFilter::Info,
+
+            gpu         : GpuSelector::default(),
+            window_mode : WindowMode::Windowed{ resolution: (800, 600) },
+
+            quality : Quality::default(),
+            budgets : BudgetOverrides::default(),
+
+            tick_rate    : DEFAULT_TICK_RATE,
+            idle_timeout : DEFAULT_IDLE_TIMEOUT,
+        }
+    }
 }
 
 impl Settings {
@@ -4,7 +4,7 @@
  * Created:
  *   26 Mar 2022, 10:53:41
  * Last edited:
- *   11 Jul 2022, 18:52:46
+ *   17 Oct 2026, 04:41:03
  * Auto updated?
  *   Yes
  *
@@ -16,6 +16,8 @@
 
 /// The module that contains this crate's errors.
 pub mod errors;
+/// The module that resolves the platform-specific directories.
+pub mod paths;
 /// The module that handles public interfaces.
 pub mod spec;
 /// The module that handles the CLI-part of this crate.
@@ -0,0 +1,189 @@
+//  PATHS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:58:31
+//  Last edited:
+//    17 Oct 2026, 05:37:17
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Resolves the platform-specific directories where the game keeps its
+//!   configuration, saves, caches and logs.
+// 
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub use crate::errors::ConfigError as Error;
+
+
+/***** CONSTANTS *****/
+/// The name of the folder we create in the platform's directories.
+const APP_DIR: &str = "Game-Rust";
+
+/// The name of the settings file. If it lives next to the executable, we consider the install to be portable.
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// The environment variable that overrides the config directory.
+pub const CONFIG_DIR_ENV: &str = "GAME_RUST_CONFIG_DIR";
+/// The environment variable that overrides the save directory.
+pub const SAVE_DIR_ENV: &str = "GAME_RUST_SAVE_DIR";
+/// The environment variable that overrides the cache directory.
+pub const CACHE_DIR_ENV: &str = "GAME_RUST_CACHE_DIR";
+/// The environment variable that overrides the log directory.
+pub const LOG_DIR_ENV: &str = "GAME_RUST_LOG_DIR";
+/// The environment variable that overrides the asset directory.
+pub const ASSET_DIR_ENV: &str = "GAME_RUST_ASSET_DIR";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the directory of the executable.
+/// 
+/// # Errors
+/// This function errors if we could not find the executable or its parent directory.
+fn exec_dir() -> Result<PathBuf, Error> {
+    let exec_path = match env::current_exe() {
+        Ok(path) => path,
+        Err(err) => { return Err(Error::ExecutablePathError{ err }); }
+    };
+    match exec_path.parent() {
+        Some(path) => Ok(path.to_path_buf()),
+        None       => Err(Error::PathParentError{ path: exec_path }),
+    }
+}
+
+/// Returns the given platform directory with the game's folder appended.
+/// 
+/// # Arguments
+/// - `what`: What kind of directory we are resolving (used for debugging).
+/// - `base`: The platform directory as returned by the `dirs` crate.
+/// 
+/// # Errors
+/// This function errors if the platform does not define the directory.
+fn platform_dir(what: &'static str, base: Option<PathBuf>) -> Result<PathBuf, Error> {
+    match base {
+        Some(base) => Ok(base.join(APP_DIR)),
+        None       => Err(Error::NoPlatformDir{ what }),
+    }
+}
+
+/// Resolves a directory by checking, in order, the CLI override, the environment variable and finally the default.
+/// 
+/// # Arguments
+/// - `cli`: The path given on the CLI, if any.
+/// - `env_var`: The name of the environment variable that may override the path.
+/// - `default`: Closure that computes the default path if neither is given.
+/// 
+/// # Errors
+/// This function only errors if the default has to be computed and that fails.
+fn resolve_dir(cli: Option<PathBuf>, env_var: &str, default: impl FnOnce() -> Result<PathBuf, Error>) -> Result<PathBuf, Error> {
+    if let Some(path) = cli { return Ok(path); }
+    if let Some(path) = env::var_os(env_var) {
+        if !path.is_empty() { return Ok(PathBuf::from(path)); }
+    }
+    default()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Collects the (optional) overrides for the directories, as given on the CLI.
+#[derive(Clone, Debug, Default)]
+pub struct DirOverrides {
+    /// Overrides the config directory.
+    pub config : Option<PathBuf>,
+    /// Overrides the save directory.
+    pub saves  : Option<PathBuf>,
+    /// Overrides the cache directory.
+    pub cache  : Option<PathBuf>,
+    /// Overrides the log directory.
+    pub logs   : Option<PathBuf>,
+    /// Overrides the asset directory.
+    pub assets : Option<PathBuf>,
+}
+
+
+
+/// The platform-specific directories where the game keeps its files.
+/// 
+/// Every directory is resolved as: CLI override > environment variable > default. The default depends on the install:
+/// - If there is a `settings.json` next to the executable, the install is portable and everything lives next to the executable (as it always did).
+/// - Otherwise, the platform's config, data and cache directories are used (e.g., `~/.config/Game-Rust` on Linux or `%APPDATA%\Game-Rust` on Windows).
+/// 
+/// The assets are read-only and ship with the executable, so they default to the `assets` folder next to it in either case.
+#[derive(Clone, Debug)]
+pub struct PlatformPaths {
+    /// The directory with the settings file.
+    pub config : PathBuf,
+    /// The directory with the save games.
+    pub saves  : PathBuf,
+    /// The directory with (re-generatable) cached files.
+    pub cache  : PathBuf,
+    /// The directory with the log files.
+    pub logs   : PathBuf,
+    /// The directory with the game's assets (textures, meshes, ...).
+    pub assets : PathBuf,
+}
+
+impl PlatformPaths {
+    /// Constructor for the PlatformPaths, which resolves every directory.
+    /// 
+    /// Note that this does not create any directories; use `ensure_dir()` right before a directory is needed.
+    /// 
+    /// # Arguments
+    /// - `overrides`: The overrides given on the CLI.
+    /// 
+    /// # Returns
+    /// A new PlatformPaths with resolved directories.
+    /// 
+    /// # Errors
+    /// This function errors if the location of the executable could not be found, or if the platform does not define a directory we need a default for.
+    pub fn new(overrides: DirOverrides) -> Result<Self, Error> {
+        // Check if this is a portable install
+        let exec_dir: PathBuf = exec_dir()?;
+        let portable: bool    = exec_dir.join(SETTINGS_FILE).is_file();
+
+        // Resolve the directories
+        let config = resolve_dir(overrides.config, CONFIG_DIR_ENV, || if portable { Ok(exec_dir.clone()) } else { platform_dir("config", dirs::config_dir()) })?;
+        let saves  = resolve_dir(overrides.saves, SAVE_DIR_ENV, || if portable { Ok(exec_dir.join("saves")) } else { Ok(platform_dir("data", dirs::data_local_dir())?.join("saves")) })?;
+        let cache  = resolve_dir(overrides.cache, CACHE_DIR_ENV, || if portable { Ok(exec_dir.join("cache")) } else { platform_dir("cache", dirs::cache_dir()) })?;
+        let logs   = resolve_dir(overrides.logs, LOG_DIR_ENV, || if portable { Ok(exec_dir.join("logs")) } else { Ok(platform_dir("data", dirs::data_local_dir())?.join("logs")) })?;
+        let assets = resolve_dir(overrides.assets, ASSET_DIR_ENV, || Ok(exec_dir.join("assets")))?;
+
+        // Done
+        Ok(Self {
+            config,
+            saves,
+            cache,
+            logs,
+            assets,
+        })
+    }
+}
+
+
+
+/// Makes sure the given directory exists, creating it (and its parents) if it doesn't.
+/// 
+/// # Arguments
+/// - `what`: What kind of directory this is (used for debugging).
+/// - `path`: The path of the directory.
+/// 
+/// # Errors
+/// This function errors if the path exists but is not a directory, or if we failed to create it.
+pub fn ensure_dir(what: &'static str, path: &Path) -> Result<(), Error> {
+    if path.is_dir() { return Ok(()); }
+    if path.exists() { return Err(Error::NotADirectory{ what, path: path.to_path_buf() }); }
+    match fs::create_dir_all(path) {
+        Ok(_)    => Ok(()),
+        Err(err) => Err(Error::DirCreateError{ what, path: path.to_path_buf(), err }),
+    }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    11 Jul 2022, 18:52:17
 //  Last edited:
-//    06 Aug 2022, 17:46:18
+//    17 Oct 2026, 05:17:43
 //  Auto updated?
 //    Yes
 // 
@@ -13,6 +13,7 @@
 // 
 
 use std::env;
+use std::fmt::{Display, Formatter, Result as FResult};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
@@ -20,6 +21,7 @@ use chrono::Local;
 use serde::{Deserialize, Serialize};
 
 pub use crate::errors::{ConfigError, SettingsError};
+use crate::paths::SETTINGS_FILE;
 
 
 /***** HELPER FUNCTIONS *****/
@@ -73,24 +75,8 @@ pub fn reresolve_path<P: AsRef<Path>>(path: P) -> Result<PathBuf, ConfigError> {
 
 
 /***** LIBRARY *****/
-/// Contains the runtime-generated locations of important directories
-#[derive(Debug)]
-pub struct DirConfig {
-    /// The location of the log files
-    pub logs : PathBuf,
-}
-
-impl DirConfig {
-    /// Constructor for the DirConfig, which will generate the locations of directories relative to the executable.
-    /// 
-    /// # Returns
-    /// A new DirConfig instance with generated paths on success, or else an Error.
-    pub fn new() -> Result<Self, ConfigError> {
-        Ok(Self {
-            logs : reresolve_path(PathBuf::from("./logs"))?,
-        })
-    }
-}
+/// Contains the runtime-generated locations of important directories (see the `paths` module).
+pub use crate::paths::PlatformPaths as DirConfig;
 
 
 
@@ -104,7 +90,7 @@ pub struct FileConfig {
 }
 
 impl FileConfig {
-    /// Constructor for the FileConfig, which will generate the locations of files relative to the executable.
+    /// Constructor for the FileConfig, which will generate the locations of files in the resolved directories.
     /// 
     /// # Arguments
     /// The newly generated DirConfig to derive nested paths from.
@@ -117,7 +103,7 @@ impl FileConfig {
 
         // Use that to populate (and return) the struct
         Ok(Self {
-            settings : reresolve_path(PathBuf::from("./settings.json"))?,
+            settings : dir_config.config.join(SETTINGS_FILE),
             log      : dir_config.logs.join(now),
         })
     }
@@ -172,3 +158,143 @@ impl FromStr for Resolution {
         Ok(Self(width, height))
     }
 }
+
+
+
+/// Selects the GPU to render on, either by index or by (part of) its name.
+/// 
+/// In the settings file, a number selects by index and a string by name. On the command line, anything that isn't a number selects by name.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum GpuSelector {
+    /// The GPU with the given index, as listed by 'game-list gpus'.
+    Index(usize),
+    /// The first supported GPU whose name contains the given string (case-insensitive).
+    Name(String),
+}
+
+impl Default for GpuSelector {
+    #[inline]
+    fn default() -> Self { Self::Index(0) }
+}
+
+impl Display for GpuSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        use GpuSelector::*;
+        match self {
+            Index(index) => write!(f, "{}", index),
+            Name(name)   => write!(f, "{}", name),
+        }
+    }
+}
+
+impl FromStr for GpuSelector {
+    type Err = SettingsError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() { return Err(SettingsError::EmptyGpuName); }
+        match usize::from_str(value) {
+            Ok(index) => Ok(Self::Index(index)),
+            Err(_)    => Ok(Self::Name(value.into())),
+        }
+    }
+}
+
+
+
+/// The quality presets that map to resource budgets.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quality {
+    /// For weak, integrated GPUs.
+    Low,
+    /// For mainstream GPUs.
+    #[default]
+    Medium,
+    /// For recent, dedicated GPUs.
+    High,
+    /// For high-end GPUs.
+    Ultra,
+}
+
+impl Display for Quality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        use Quality::*;
+        match self {
+            Low    => write!(f, "low"),
+            Medium => write!(f, "medium"),
+            High   => write!(f, "high"),
+            Ultra  => write!(f, "ultra"),
+        }
+    }
+}
+
+impl FromStr for Quality {
+    type Err = SettingsError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "low"    => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high"   => Ok(Self::High),
+            "ultra"  => Ok(Self::Ultra),
+            _        => Err(SettingsError::UnknownQuality{ raw: value.into() }),
+        }
+    }
+}
+
+
+
+/// The resource budgets that subsystems should stay within.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Budgets {
+    /// The amount of VRAM that textures may use, in MiB.
+    pub texture_vram_mb   : u32,
+    /// The maximum number of live particles.
+    pub max_particles     : u32,
+    /// The width and height of shadow maps, in pixels.
+    pub shadow_resolution : u32,
+    /// The distance (in world units) beyond which objects are not drawn.
+    pub draw_distance     : f32,
+}
+
+impl Budgets {
+    /// Returns the budgets for the given quality preset.
+    pub fn preset(quality: Quality) -> Self {
+        match quality {
+            Quality::Low    => Self{ texture_vram_mb: 512, max_particles: 1000, shadow_resolution: 512, draw_distance: 250.0 },
+            Quality::Medium => Self{ texture_vram_mb: 1024, max_particles: 5000, shadow_resolution: 1024, draw_distance: 500.0 },
+            Quality::High   => Self{ texture_vram_mb: 2048, max_particles: 20000, shadow_resolution: 2048, draw_distance: 1000.0 },
+            Quality::Ultra  => Self{ texture_vram_mb: 4096, max_particles: 50000, shadow_resolution: 4096, draw_distance: 2000.0 },
+        }
+    }
+
+    /// Returns the budgets for the given quality preset, with the given overrides applied on top.
+    pub fn with_overrides(quality: Quality, overrides: &BudgetOverrides) -> Self {
+        let preset: Self = Self::preset(quality);
+        Self {
+            texture_vram_mb   : overrides.texture_vram_mb.unwrap_or(preset.texture_vram_mb),
+            max_particles     : overrides.max_particles.unwrap_or(preset.max_particles),
+            shadow_resolution : overrides.shadow_resolution.unwrap_or(preset.shadow_resolution),
+            draw_distance     : overrides.draw_distance.unwrap_or(preset.draw_distance),
+        }
+    }
+}
+
+/// Overrides for individual budgets in the settings file. Anything left out is taken from the quality preset.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct BudgetOverrides {
+    /// Overrides the amount of VRAM that textures may use, in MiB.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub texture_vram_mb   : Option<u32>,
+    /// Overrides the maximum number of live particles.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_particles     : Option<u32>,
+    /// Overrides the width and height of shadow maps, in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow_resolution : Option<u32>,
+    /// Overrides the distance (in world units) beyond which objects are not drawn.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub draw_distance     : Option<f32>,
+}
@@ -0,0 +1,310 @@
+//  BUS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:48:12
+//  Last edited:
+//    17 Oct 2026, 05:36:02
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements a typed, buffered message bus for communication between
+//!   systems that isn't tied to a particular entity.
+// 
+
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem;
+
+use crate::spec::Propagation;
+
+
+/***** TYPES *****/
+/// The handlers that may subscribe to messages of type `M`. They decide whether the message propagates to later handlers and readers.
+pub type Handler<M> = Box<dyn FnMut(&M) -> Propagation>;
+
+/// A published message that is waiting for the next flush, as a closure that moves it into the queue of its type.
+type Outgoing = Box<dyn FnOnce(&mut MessageBus)>;
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Panics with a message about a queue that doesn't have the type under which it is stored.
+/// 
+/// # Generic types
+/// - `M`: The type of the message for which we tried to get the queue.
+#[inline]
+fn wrong_queue<M>() -> ! { panic!("Message queue for type '{}' has another type", std::any::type_name::<M>()) }
+
+
+
+
+/***** HELPER TRAITS *****/
+/// Type-erased interface to a Queue, so the MessageBus can flush queues of every message type.
+trait AnyQueue {
+    /// Delivers the pending messages to the subscribed handlers and then to readers, dropping the ones delivered before.
+    fn flush(&mut self);
+    /// Removes the handler with the given ID.
+    /// 
+    /// # Returns
+    /// Whether the handler existed.
+    fn unsubscribe(&mut self, id: u64) -> bool;
+
+    /// Returns the queue as Any, for downcasting.
+    fn as_any(&self) -> &dyn Any;
+    /// Returns the queue as Any, for downcasting (mutably).
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+
+
+
+
+/***** HELPER STRUCTS *****/
+/// The queue of messages of a single type.
+struct Queue<M> {
+    /// The messages published since the last flush.
+    pending   : Vec<M>,
+    /// The messages delivered at the last flush (and not consumed by any handler).
+    delivered : Vec<M>,
+    /// The subscribed handlers with their IDs and priorities, highest priority first.
+    handlers  : Vec<(u64, i32, Handler<M>)>,
+}
+
+impl<M> Queue<M> {
+    /// Constructor for the Queue.
+    /// 
+    /// # Returns
+    /// A new Queue without any messages or handlers.
+    #[inline]
+    fn new() -> Self {
+        Self {
+            pending   : vec![],
+            delivered : vec![],
+            handlers  : vec![],
+        }
+    }
+}
+
+impl<M: 'static> AnyQueue for Queue<M> {
+    fn flush(&mut self) {
+        let handlers: &mut Vec<(u64, i32, Handler<M>)> = &mut self.handlers;
+        self.delivered = mem::take(&mut self.pending);
+        self.delivered.retain(|message| {
+            for (_, _, handler) in handlers.iter_mut() {
+                if handler(message) == Propagation::Consume { return false; }
+            }
+            true
+        });
+    }
+
+    fn unsubscribe(&mut self, id: u64) -> bool {
+        let len: usize = self.handlers.len();
+        self.handlers.retain(|(hid, _, _)| *hid != id);
+        self.handlers.len() < len
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn Any { self }
+    #[inline]
+    fn as_any_mut(&mut self) -> &mut dyn Any { self }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Identifies a handler subscribed with `MessageBus::subscribe()`, so it can be unsubscribed again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId {
+    /// The type of the messages the handler is subscribed to.
+    kind : TypeId,
+    /// The unique number of the handler.
+    id   : u64,
+}
+
+
+
+/// A typed message bus between systems.
+/// 
+/// Messages are not delivered the moment they are published, but buffered until the next call to `MessageBus::flush()`. The EventSystem does so once per iteration of the game loop, after all window events have been handled. From then on, the messages can be read by any system until the next flush. This way, no system observes another system's messages halfway through a frame.
+/// 
+/// Publishing only needs a shared reference, so systems can publish while they read this iteration's messages (e.g., from a tick callback). Such messages are delivered at the next flush.
+/// 
+/// Systems that want to see messages before anyone else (e.g., a UI layer that takes input first) can subscribe a handler instead. Handlers run at flush time in order of priority, and may consume a message so that later handlers and readers don't see it.
+#[derive(Default)]
+pub struct MessageBus {
+    /// The queues of messages, one per message type.
+    queues  : HashMap<TypeId, Box<dyn AnyQueue>>,
+    /// The messages published since the last flush, in the order they were published.
+    outbox  : RefCell<Vec<Outgoing>>,
+    /// The ID of the next handler to subscribe.
+    next_id : u64,
+}
+
+impl MessageBus {
+    /// Constructor for the MessageBus.
+    /// 
+    /// # Returns
+    /// A new MessageBus without any messages.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            queues  : HashMap::new(),
+            outbox  : RefCell::new(vec![]),
+            next_id : 0,
+        }
+    }
+
+
+
+    /// Publishes a new message. It is delivered at the next flush.
+    /// 
+    /// # Generic types
+    /// - `M`: The type of the message. Any type can be used, and systems read messages by this type.
+    /// 
+    /// # Arguments
+    /// - `message`: The message to publish.
+    pub fn publish<M: 'static>(&self, message: M) {
+        self.outbox.borrow_mut().push(Box::new(move |bus: &mut MessageBus| bus.queue_mut::<M>().pending.push(message)));
+    }
+
+    /// Subscribes a handler to messages of the given type. It is called for every message of that type at each flush, before the message can be read.
+    /// 
+    /// Handlers run in order of decreasing priority; handlers with the same priority run in the order in which they were subscribed. Note that the MessageBus is borrowed while handlers run, so they cannot publish messages themselves.
+    /// 
+    /// # Generic types
+    /// - `M`: The type of the messages to handle.
+    /// 
+    /// # Arguments
+    /// - `priority`: The priority of the handler. Higher priorities see messages first.
+    /// - `handler`: The handler to call. Returning `Propagation::Consume` hides the message from the remaining handlers and from readers.
+    /// 
+    /// # Returns
+    /// A SubscriptionId that can be passed to `MessageBus::unsubscribe()`.
+    pub fn subscribe<M: 'static, F: 'static + FnMut(&M) -> Propagation>(&mut self, priority: i32, handler: F) -> SubscriptionId {
+        let id: u64 = self.next_id;
+        self.next_id += 1;
+
+        let handlers: &mut Vec<(u64, i32, Handler<M>)> = &mut self.queue_mut::<M>().handlers;
+        let index: usize = handlers.iter().position(|(_, p, _)| *p < priority).unwrap_or(handlers.len());
+        handlers.insert(index, (id, priority, Box::new(handler)));
+        SubscriptionId{ kind: TypeId::of::<M>(), id }
+    }
+
+    /// Removes a handler subscribed with `MessageBus::subscribe()`.
+    /// 
+    /// # Arguments
+    /// - `id`: The SubscriptionId of the handler.
+    /// 
+    /// # Returns
+    /// Whether the handler was still subscribed.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        match self.queues.get_mut(&id.kind) {
+            Some(queue) => queue.unsubscribe(id.id),
+            None        => false,
+        }
+    }
+
+    /// Returns the messages of the given type that were delivered at the last flush.
+    /// 
+    /// # Generic types
+    /// - `M`: The type of the messages to read.
+    /// 
+    /// # Returns
+    /// A slice with the messages, in the order they were published. Is empty if there are none.
+    pub fn read<M: 'static>(&self) -> &[M] {
+        match self.queues.get(&TypeId::of::<M>()) {
+            Some(queue) => &queue.as_any().downcast_ref::<Queue<M>>().unwrap_or_else(|| wrong_queue::<M>()).delivered,
+            None        => &[],
+        }
+    }
+
+
+
+    /// Delivers all pending messages, first to the subscribed handlers and then making the ones that weren't consumed readable until the next flush. Messages delivered at the previous flush are dropped.
+    pub fn flush(&mut self) {
+        let outbox: Vec<Outgoing> = self.outbox.take();
+        for outgoing in outbox {
+            outgoing(self);
+        }
+        for queue in self.queues.values_mut() {
+            queue.flush();
+        }
+    }
+
+
+
+    /// Returns the queue for messages of the given type, creating it if it doesn't exist yet.
+    fn queue_mut<M: 'static>(&mut self) -> &mut Queue<M> {
+        let queue: &mut Box<dyn AnyQueue> = self.queues.entry(TypeId::of::<M>()).or_insert_with(|| Box::new(Queue::<M>::new()));
+        queue.as_any_mut().downcast_mut::<Queue<M>>().unwrap_or_else(|| wrong_queue::<M>())
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    /// A message that one system sends to another.
+    #[derive(Debug, PartialEq)]
+    struct Ping(u32);
+
+
+    #[test]
+    fn test_publish_is_buffered_until_flush() {
+        let mut bus = MessageBus::new();
+        bus.publish(Ping(1));
+        assert!(bus.read::<Ping>().is_empty());
+
+        bus.flush();
+        assert_eq!(bus.read::<Ping>(), &[ Ping(1) ]);
+
+        // Delivered messages only live until the next flush
+        bus.flush();
+        assert!(bus.read::<Ping>().is_empty());
+    }
+
+    #[test]
+    fn test_tick_publishes_for_other_system() {
+        let mut bus = MessageBus::new();
+        let mut received: Vec<u32> = vec![];
+
+        // Two systems that only get a shared reference to the bus, like tick callbacks do
+        let sender       = |tick: u32, bus: &MessageBus| bus.publish(Ping(tick));
+        let mut receiver = |bus: &MessageBus| received.extend(bus.read::<Ping>().iter().map(|p| p.0));
+
+        for tick in 0..3 {
+            sender(tick, &bus);
+            receiver(&bus);
+            bus.flush();
+        }
+        receiver(&bus);
+
+        // Each message is seen exactly once, one iteration after it was sent
+        assert_eq!(received, vec![ 0, 1, 2 ]);
+    }
+
+    #[test]
+    fn test_subscribers_can_consume() {
+        let mut bus = MessageBus::new();
+        bus.subscribe::<Ping, _>(0, |_| Propagation::Continue);
+        bus.subscribe::<Ping, _>(10, |p| if p.0 == 2 { Propagation::Consume } else { Propagation::Continue });
+
+        bus.publish(Ping(1));
+        bus.publish(Ping(2));
+        bus.flush();
+        assert_eq!(bus.read::<Ping>(), &[ Ping(1) ]);
+    }
+}
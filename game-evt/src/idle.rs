@@ -0,0 +1,198 @@
+//  IDLE.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:19:23
+//  Last edited:
+//    17 Oct 2026, 05:54:11
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements tracking whether the game is idle in the background, so
+//!   it can trim its resources and render less often.
+// 
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use log::info;
+use winit::window::WindowId;
+
+
+/***** CONSTANTS *****/
+/// The number of frames per second the game loop runs at while idle.
+pub const IDLE_FRAME_RATE: u32 = 10;
+
+
+
+
+
+/***** LIBRARY *****/
+/// Tracks whether the game has been in the background (i.e., none of its windows had focus) for long enough to be considered idle.
+#[derive(Clone, Debug)]
+pub struct Idle {
+    /// How long the game has to be in the background before it is idle, or None to never become idle.
+    timeout : Option<Duration>,
+
+    /// The windows that currently have focus.
+    focused    : HashSet<WindowId>,
+    /// The moment the last focused window lost focus (or the tracker was created, if none ever had it), if none of them has it.
+    background : Option<Instant>,
+    /// Whether the game is idle.
+    idle       : bool,
+    /// The moment the previous idle frame started.
+    last_frame : Instant,
+}
+
+impl Idle {
+    /// Constructor for the Idle tracker.
+    /// 
+    /// # Arguments
+    /// - `timeout`: How long the game has to be in the background before it is idle, or None to never become idle.
+    /// 
+    /// # Returns
+    /// A new Idle tracker that assumes the game is in the background until one of its windows gains focus, since not all platforms report the initial focus (e.g., when the game is started behind another window).
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+
+            focused    : HashSet::new(),
+            background : Some(Instant::now()),
+            idle       : false,
+            last_frame : Instant::now(),
+        }
+    }
+
+
+
+    /// Records that a window gained or lost focus.
+    /// 
+    /// The game only goes to the background once none of its windows has focus, so moving the focus from one window to another does not count.
+    /// 
+    /// # Arguments
+    /// - `window`: The window that gained or lost focus.
+    /// - `focused`: Whether the window gained (true) or lost (false) focus.
+    pub(crate) fn set_focused(&mut self, window: WindowId, focused: bool) {
+        if focused {
+            self.focused.insert(window);
+            self.background = None;
+        } else {
+            self.focused.remove(&window);
+            if self.focused.is_empty() && self.background.is_none() { self.background = Some(Instant::now()); }
+        }
+    }
+
+    /// Updates whether the game is idle. Should be called once per game tick.
+    /// 
+    /// # Returns
+    /// `Some(true)` if the game just became idle, `Some(false)` if it just stopped being idle, or None if nothing changed.
+    pub(crate) fn update(&mut self) -> Option<bool> {
+        let now: Instant = Instant::now();
+        self.last_frame = now;
+
+        let idle: bool = match (self.timeout, self.background) {
+            (Some(timeout), Some(background)) => now - background >= timeout,
+            _                                 => false,
+        };
+        if idle == self.idle { return None; }
+        self.idle = idle;
+        if idle { info!("Game is idle in the background; trimming resources and limiting to {} FPS", IDLE_FRAME_RATE); }
+        else    { info!("Game is no longer idle"); }
+        Some(idle)
+    }
+
+
+
+    /// Sets how long the game has to be in the background before it is idle.
+    /// 
+    /// # Arguments
+    /// - `timeout`: The new timeout, or None to never become idle. Takes effect at the next game tick.
+    #[inline]
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) { self.timeout = timeout; }
+
+
+
+    /// Returns whether the game is idle.
+    #[inline]
+    pub fn is_idle(&self) -> bool { self.idle }
+
+    /// Returns whether one of the windows has focus.
+    #[inline]
+    pub fn is_focused(&self) -> bool { self.background.is_none() }
+
+    /// Returns how long the game has to be in the background before it is idle, or None if it never becomes idle.
+    #[inline]
+    pub fn timeout(&self) -> Option<Duration> { self.timeout }
+
+    /// Returns when the next frame should start if the game is idle, so that it runs at `IDLE_FRAME_RATE`.
+    /// 
+    /// # Returns
+    /// The moment of the next frame, or None if the game is not idle and should run at full speed.
+    #[inline]
+    pub fn next_frame(&self) -> Option<Instant> {
+        if self.idle { Some(self.last_frame + Duration::from_secs(1) / IDLE_FRAME_RATE) } else { None }
+    }
+}
+
+impl Default for Idle {
+    /// Creates an Idle tracker that never considers the game idle.
+    #[inline]
+    fn default() -> Self { Self::new(None) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_idle_only_without_focused_windows() {
+        // winit can only give us dummy IDs outside of an EventLoop, which all compare equal
+        let window: WindowId = unsafe { WindowId::dummy() };
+        let mut idle = Idle::new(Some(Duration::ZERO));
+
+        // Losing the focus of the last window puts the game in the background
+        idle.set_focused(window, true);
+        assert!(idle.is_focused());
+        assert_eq!(idle.update(), None);
+        idle.set_focused(window, false);
+        assert!(!idle.is_focused());
+        assert_eq!(idle.update(), Some(true));
+        assert!(idle.is_idle());
+
+        // Losing it again (e.g., when the window is destroyed) changes nothing
+        idle.set_focused(window, false);
+        assert_eq!(idle.update(), None);
+
+        // And regaining it ends it
+        idle.set_focused(window, true);
+        assert_eq!(idle.update(), Some(false));
+    }
+
+    #[test]
+    fn test_idle_without_ever_gaining_focus() {
+        let window: WindowId = unsafe { WindowId::dummy() };
+
+        // A game that never receives a focus event still goes idle
+        let mut idle = Idle::new(Some(Duration::ZERO));
+        assert!(!idle.is_focused());
+        assert_eq!(idle.update(), Some(true));
+        assert!(idle.is_idle());
+
+        // Until its window gains focus for the first time
+        idle.set_focused(window, true);
+        assert!(idle.is_focused());
+        assert_eq!(idle.update(), Some(false));
+
+        // Without a timeout, it never goes idle at all
+        let mut idle = Idle::default();
+        assert_eq!(idle.update(), None);
+        assert!(!idle.is_idle());
+    }
+}
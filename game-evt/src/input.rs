@@ -0,0 +1,247 @@
+//  INPUT.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:00:00
+//  Last edited:
+//    17 Oct 2026, 05:39:10
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the collection of keyboard and mouse input, which is
+//!   delivered to gameplay code before every game tick.
+// 
+
+use std::collections::HashSet;
+
+use winit::dpi::PhysicalPosition;
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent as WinitWindowEvent};
+use winit::window::WindowId;
+
+use crate::bus::MessageBus;
+use crate::spec::InputEvent;
+
+
+/***** CONSTANTS *****/
+/// The number of pixels we count as one line when converting touchpad scrolls.
+const PIXELS_PER_LINE: f64 = 20.0;
+
+
+
+
+
+/***** TYPES *****/
+/// The callbacks that may be registered for InputEvents. They receive the event and the InputState after the event has been applied.
+pub type InputCallback = Box<dyn FnMut(&InputEvent, &InputState)>;
+
+
+
+
+
+/***** LIBRARY *****/
+/// The state of the keyboard and mouse as of the start of the current game tick.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+    /// The keys that are currently held down.
+    keys    : HashSet<VirtualKeyCode>,
+    /// The mouse buttons that are currently held down.
+    buttons : HashSet<MouseButton>,
+    /// The last known position of the cursor (in pixels, relative to the top-left of the Window it is in), if any.
+    cursor  : Option<(f64, f64)>,
+    /// The total scroll distance (in lines) since the previous tick.
+    scroll  : (f32, f32),
+}
+
+impl InputState {
+    /// Updates the state with the given event.
+    /// 
+    /// # Arguments
+    /// - `event`: The InputEvent to process.
+    fn apply(&mut self, event: &InputEvent) {
+        match event {
+            InputEvent::KeyPressed(_, key)  => { self.keys.insert(*key); },
+            InputEvent::KeyReleased(_, key) => { self.keys.remove(key); },
+
+            InputEvent::MouseButtonPressed(_, button)  => { self.buttons.insert(*button); },
+            InputEvent::MouseButtonReleased(_, button) => { self.buttons.remove(button); },
+
+            InputEvent::CursorMoved{ x, y, .. } => { self.cursor = Some((*x, *y)); },
+            InputEvent::MouseWheel{ dx, dy, .. } => { self.scroll.0 += dx; self.scroll.1 += dy; },
+        }
+    }
+
+
+
+    /// Returns whether the given key is currently held down.
+    #[inline]
+    pub fn is_key_down(&self, key: VirtualKeyCode) -> bool { self.keys.contains(&key) }
+
+    /// Returns whether the given mouse button is currently held down.
+    #[inline]
+    pub fn is_button_down(&self, button: MouseButton) -> bool { self.buttons.contains(&button) }
+
+    /// Returns the last known position of the cursor (in pixels, relative to the top-left of the Window it is in), or None if it hasn't moved over any Window yet.
+    #[inline]
+    pub fn cursor(&self) -> Option<(f64, f64)> { self.cursor }
+
+    /// Returns the horizontal and vertical scroll distance (in lines) since the previous tick.
+    #[inline]
+    pub fn scroll(&self) -> (f32, f32) { self.scroll }
+}
+
+
+
+/// Collects input events from winit and delivers them once per game tick.
+pub struct Input {
+    /// The events that occurred since the previous tick.
+    pending   : Vec<InputEvent>,
+    /// The state of the keyboard and mouse.
+    state     : InputState,
+    /// The callbacks that are called for every InputEvent.
+    callbacks : Vec<InputCallback>,
+}
+
+impl Input {
+    /// Constructor for the Input.
+    /// 
+    /// # Returns
+    /// A new Input without any pending events or callbacks.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            pending   : vec![],
+            state     : InputState::default(),
+            callbacks : vec![],
+        }
+    }
+
+
+
+    /// Registers a callback that is called for every InputEvent, just before the game tick in which it is delivered.
+    /// 
+    /// Callbacks are called in the order in which they were registered.
+    /// 
+    /// # Arguments
+    /// - `callback`: The callback to call. It receives the event and the InputState after the event has been applied.
+    pub fn on_input<F: 'static + FnMut(&InputEvent, &InputState)>(&mut self, callback: F) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// Converts the given winit WindowEvent to an InputEvent and queues it for the next tick.
+    /// 
+    /// Events that are not input events (or keys that winit could not map) are ignored. When a Window loses focus, a release is queued for every key and mouse button that is still held down, as we will not see them being released anymore.
+    /// 
+    /// # Arguments
+    /// - `window`: The Window for which the event occurred.
+    /// - `event`: The winit WindowEvent to process.
+    pub(crate) fn record(&mut self, window: WindowId, event: &WinitWindowEvent) {
+        let event: InputEvent = match event {
+            WinitWindowEvent::KeyboardInput{ input, .. } => {
+                let key: VirtualKeyCode = match input.virtual_keycode {
+                    Some(key) => key,
+                    None      => { return; }
+                };
+                match input.state {
+                    ElementState::Pressed  => InputEvent::KeyPressed(window, key),
+                    ElementState::Released => InputEvent::KeyReleased(window, key),
+                }
+            },
+
+            WinitWindowEvent::MouseInput{ state, button, .. } => match state {
+                ElementState::Pressed  => InputEvent::MouseButtonPressed(window, *button),
+                ElementState::Released => InputEvent::MouseButtonReleased(window, *button),
+            },
+
+            WinitWindowEvent::CursorMoved{ position, .. } => InputEvent::CursorMoved{ window, x: position.x, y: position.y },
+            WinitWindowEvent::MouseWheel{ delta, .. } => {
+                let (dx, dy): (f32, f32) = match delta {
+                    MouseScrollDelta::LineDelta(dx, dy)                    => (*dx, *dy),
+                    MouseScrollDelta::PixelDelta(PhysicalPosition{ x, y }) => ((x / PIXELS_PER_LINE) as f32, (y / PIXELS_PER_LINE) as f32),
+                };
+                InputEvent::MouseWheel{ window, dx, dy }
+            },
+
+            // Release everything that is held down once we lose focus
+            WinitWindowEvent::Focused(false) => {
+                // Find what will still be held after the events we already have
+                let mut held: InputState = self.state.clone();
+                for event in &self.pending { held.apply(event); }
+
+                self.pending.extend(held.keys.into_iter().map(|key| InputEvent::KeyReleased(window, key)));
+                self.pending.extend(held.buttons.into_iter().map(|button| InputEvent::MouseButtonReleased(window, button)));
+                return;
+            },
+
+            // The rest is not input
+            _ => { return; }
+        };
+        self.pending.push(event);
+    }
+
+    /// Delivers the events collected since the previous tick.
+    /// 
+    /// Applies every event to the InputState, calls the registered callbacks and publishes the event on the given MessageBus (so it can be read with `MessageBus::read::<InputEvent>()` after the next flush).
+    /// 
+    /// # Arguments
+    /// - `bus`: The MessageBus to publish the events on.
+    pub(crate) fn deliver(&mut self, bus: &mut MessageBus) {
+        // The scroll distance is per-tick
+        self.state.scroll = (0.0, 0.0);
+
+        for event in self.pending.drain(..) {
+            self.state.apply(&event);
+            for callback in &mut self.callbacks {
+                callback(&event, &self.state);
+            }
+            bus.publish(event);
+        }
+    }
+
+
+
+    /// Returns the state of the keyboard and mouse as of the start of the current tick.
+    #[inline]
+    pub fn state(&self) -> &InputState { &self.state }
+}
+
+impl Default for Input {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_focus_loss_releases_held_input() {
+        let mut input = Input::new();
+        let mut bus   = MessageBus::new();
+        let window: WindowId = unsafe { WindowId::dummy() };
+
+        // Hold a key and a button, and release another key before losing focus in the same tick
+        input.pending.push(InputEvent::KeyPressed(window, VirtualKeyCode::W));
+        input.pending.push(InputEvent::KeyPressed(window, VirtualKeyCode::A));
+        input.pending.push(InputEvent::MouseButtonPressed(window, MouseButton::Left));
+        input.deliver(&mut bus);
+        input.pending.push(InputEvent::KeyReleased(window, VirtualKeyCode::A));
+        input.record(window, &WinitWindowEvent::Focused(false));
+        input.deliver(&mut bus);
+
+        assert!(!input.state().is_key_down(VirtualKeyCode::W));
+        assert!(!input.state().is_key_down(VirtualKeyCode::A));
+        assert!(!input.state().is_button_down(MouseButton::Left));
+
+        // Only the keys that were still held get a (single) release
+        bus.flush();
+        let releases: usize = bus.read::<InputEvent>().iter().filter(|e| matches!(e, InputEvent::KeyReleased(..) | InputEvent::MouseButtonReleased(..))).count();
+        assert_eq!(releases, 3);
+    }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    18 Jul 2022, 18:29:26
 //  Last edited:
-//    07 Aug 2022, 18:17:12
+//    17 Oct 2026, 05:21:18
 //  Auto updated?
 //    Yes
 // 
@@ -16,7 +16,17 @@
 // Define the submodules of this crate
 pub mod errors;
 pub mod spec;
+pub mod bus;
+pub mod idle;
+pub mod input;
+pub mod stats;
 pub mod system;
+pub mod time;
 
 // Pull some things into the crate namespace
-pub use system::{Error, EventSystem};
+pub use bus::{MessageBus, SubscriptionId};
+pub use idle::Idle;
+pub use input::{Input, InputState};
+pub use stats::{FrameStats, SystemStats};
+pub use system::{Error, EventSystem, FixedCallback, TickCallback};
+pub use time::Time;
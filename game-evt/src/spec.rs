@@ -4,7 +4,7 @@
 //  Created:
 //    18 Jul 2022, 18:42:16
 //  Last edited:
-//    07 Aug 2022, 18:06:05
+//    17 Oct 2026, 05:48:35
 //  Auto updated?
 //    Yes
 // 
@@ -12,8 +12,11 @@
 //!   Defines (public) interfaces and structs for the EventSystem.
 // 
 
+use winit::event::{MouseButton, VirtualKeyCode};
 use winit::window::WindowId;
 
+use game_gfx::spec::RenderBudgets;
+
 pub use crate::errors::EventError as Error;
 
 
@@ -32,3 +35,96 @@ pub enum Event {
     /// Contains whether the game quits naturally (None) or due to an Error (in which case it describes it).
     Exit(Option<Error>),
 }
+
+
+
+/// Defines the keyboard and mouse events that gameplay code may react to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputEvent {
+    /// A key was pressed (or repeated, while held down).
+    /// 
+    /// Contains the Window that had focus and the key that was pressed.
+    KeyPressed(WindowId, VirtualKeyCode),
+    /// A key was released.
+    /// 
+    /// Contains the Window that had focus and the key that was released.
+    KeyReleased(WindowId, VirtualKeyCode),
+
+    /// A mouse button was pressed.
+    /// 
+    /// Contains the Window that had focus and the button that was pressed.
+    MouseButtonPressed(WindowId, MouseButton),
+    /// A mouse button was released.
+    /// 
+    /// Contains the Window that had focus and the button that was released.
+    MouseButtonReleased(WindowId, MouseButton),
+
+    /// The cursor moved within a Window.
+    CursorMoved {
+        /// The Window in which the cursor moved.
+        window : WindowId,
+        /// The new X-coordinate of the cursor, in pixels from the left of the Window.
+        x      : f64,
+        /// The new Y-coordinate of the cursor, in pixels from the top of the Window.
+        y      : f64,
+    },
+    /// The mouse wheel (or touchpad) scrolled.
+    MouseWheel {
+        /// The Window that had focus.
+        window : WindowId,
+        /// The horizontal scroll distance, in lines.
+        dx     : f32,
+        /// The vertical scroll distance, in lines (positive is away from the user).
+        dy     : f32,
+    },
+}
+
+
+
+/// Published on the MessageBus whenever a Window is resized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowResized {
+    /// The Window that was resized.
+    pub window : WindowId,
+    /// The new width of the Window's surface, in pixels.
+    pub width  : u32,
+    /// The new height of the Window's surface, in pixels.
+    pub height : u32,
+}
+
+
+
+/// Determines what happens to a message after a subscribed handler has seen it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Propagation {
+    /// The message is passed on to the next handler, and is readable from the MessageBus afterwards.
+    Continue,
+    /// The message is consumed: later handlers don't see it, and it cannot be read from the MessageBus.
+    Consume,
+}
+
+
+
+/// Published on the MessageBus when the game becomes idle in the background, or stops being so (see `Idle`).
+/// 
+/// Systems can use it to trim their caches while idle, and to restore them afterwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdleEvent {
+    /// None of the game's windows have had focus for the idle timeout.
+    Entered,
+    /// One of the game's windows regained focus.
+    Left,
+}
+
+
+
+/// Published on the MessageBus when the resource budgets change at runtime (e.g., because the player picked another quality preset).
+/// 
+/// The EventSystem passes the render budgets on to the RenderSystem when it is delivered; other systems can read it to re-apply their own budgets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BudgetsChanged {
+    /// The new budgets for rendering.
+    pub render        : RenderBudgets,
+    /// The new maximum number of live particles.
+    pub max_particles : u32,
+}
@@ -0,0 +1,263 @@
+//  STATS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:07:55
+//  Last edited:
+//    17 Oct 2026, 05:53:49
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements rolling frame time statistics, for the frames as a
+//!   whole and for the systems that run in them.
+// 
+
+use std::collections::{BTreeMap, VecDeque};
+#[cfg(feature = "tracy")]
+use std::fmt::{Debug, Formatter, Result as FResult};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+
+
+/***** CONSTANTS *****/
+/// The number of frames over which the statistics are computed.
+const WINDOW: usize = 120;
+
+/// The interval at which the statistics are logged, if enabled.
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Converts a Duration to fractional milliseconds.
+#[inline]
+fn ms(duration: Duration) -> f64 { duration.as_secs_f64() * 1000.0 }
+
+
+
+/// Wraps the name of a Tracy plot for a system, so that it can live in the (Debug) SystemStats.
+#[cfg(feature = "tracy")]
+#[derive(Clone, Copy)]
+struct SystemPlot(tracy_client::PlotName);
+
+#[cfg(feature = "tracy")]
+impl Debug for SystemPlot {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "SystemPlot") }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Keeps rolling statistics over the execution times of a single system in the most recent frames, and checks them against its budget.
+#[derive(Clone, Debug, Default)]
+pub struct SystemStats {
+    /// The execution times in the most recent frames, oldest first.
+    samples : VecDeque<Duration>,
+    /// The sum of `samples`.
+    total   : Duration,
+
+    /// The time the system may take per frame, if any.
+    budget       : Option<Duration>,
+    /// The moment we last warned about an overrun.
+    last_warning : Option<Instant>,
+
+    /// The Tracy plot for the system's times, created the first time we have something to plot (since its name is leaked).
+    #[cfg(feature = "tracy")]
+    plot : Option<SystemPlot>,
+}
+
+impl SystemStats {
+    /// Records the time the system took in a frame, warning (at most once per second) if it exceeded its budget.
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the system (used for debugging).
+    /// - `time`: The time the system took in this frame.
+    fn record(&mut self, name: &str, time: Duration) {
+        self.samples.push_back(time);
+        self.total += time;
+        if self.samples.len() > WINDOW {
+            let oldest: Duration = self.samples.pop_front().unwrap();
+            self.total -= oldest;
+        }
+        #[cfg(feature = "tracy")]
+        if let Some(client) = tracy_client::Client::running() {
+            let plot: SystemPlot = *self.plot.get_or_insert_with(|| SystemPlot(tracy_client::PlotName::new_leak(format!("system: {} (ms)", name))));
+            client.plot(plot.0, ms(time));
+        }
+
+        // Check the budget
+        if let Some(budget) = self.budget {
+            if time > budget && self.last_warning.map(|last| last.elapsed() >= REPORT_INTERVAL).unwrap_or(true) {
+                self.last_warning = Some(Instant::now());
+                warn!("System '{}' took {:.2} ms, exceeding its budget of {:.2} ms (avg {:.2} ms, {} overruns in the last {} frames)", name, ms(time), ms(budget), ms(self.average()), self.overruns(), self.samples.len());
+            }
+        }
+    }
+
+
+
+    /// Returns the time the system took in the most recent frame, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn last(&self) -> Duration { self.samples.back().copied().unwrap_or_default() }
+
+    /// Returns the average time the system took over the most recent frames, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() { return Duration::ZERO; }
+        self.total / self.samples.len() as u32
+    }
+
+    /// Returns the longest time the system took over the most recent frames, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn max(&self) -> Duration { self.samples.iter().max().copied().unwrap_or_default() }
+
+    /// Returns the time the system may take per frame, if it has a budget.
+    #[inline]
+    pub fn budget(&self) -> Option<Duration> { self.budget }
+
+    /// Returns the number of the most recent frames in which the system exceeded its budget (always zero if it has none).
+    #[inline]
+    pub fn overruns(&self) -> usize {
+        match self.budget {
+            Some(budget) => self.samples.iter().filter(|time| **time > budget).count(),
+            None         => 0,
+        }
+    }
+
+    /// Returns whether the system exceeds its budget on average over the most recent frames.
+    #[inline]
+    pub fn is_over_budget(&self) -> bool { self.budget.map(|budget| self.average() > budget).unwrap_or(false) }
+}
+
+
+
+/// Keeps rolling statistics over the frame times of the most recent frames, and over the time each system took in them.
+#[derive(Clone, Debug, Default)]
+pub struct FrameStats {
+    /// The frame times of the most recent frames, oldest first.
+    samples : VecDeque<Duration>,
+    /// The sum of `samples`.
+    total   : Duration,
+    /// The statistics of the systems that run every frame, by name.
+    systems : BTreeMap<&'static str, SystemStats>,
+
+    /// Whether to log the statistics every second.
+    report       : bool,
+    /// The time since we last logged the statistics.
+    since_report : Duration,
+}
+
+impl FrameStats {
+    /// Constructor for the FrameStats.
+    /// 
+    /// # Returns
+    /// New FrameStats without any frames recorded.
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+
+
+    /// Records the time a frame took, logging the statistics if reporting is enabled and another second has passed.
+    /// 
+    /// # Arguments
+    /// - `frame_time`: The (wall-clock) time between the start of the previous frame and this one.
+    pub(crate) fn record(&mut self, frame_time: Duration) {
+        self.samples.push_back(frame_time);
+        self.total += frame_time;
+        if self.samples.len() > WINDOW {
+            let oldest: Duration = self.samples.pop_front().unwrap();
+            self.total -= oldest;
+        }
+        #[cfg(feature = "tracy")]
+        if let Some(client) = tracy_client::Client::running() {
+            client.plot(tracy_client::plot_name!("frame time (ms)"), ms(frame_time));
+            client.plot(tracy_client::plot_name!("average frame time (ms)"), ms(self.average()));
+        }
+
+        // Report if it's time
+        if self.report {
+            self.since_report += frame_time;
+            if self.since_report >= REPORT_INTERVAL {
+                self.since_report = Duration::ZERO;
+                info!("{:.1} FPS (frame time: avg {:.2} ms, min {:.2} ms, max {:.2} ms)", self.fps(), ms(self.average()), ms(self.min()), ms(self.max()));
+                for (name, stats) in self.systems.iter() {
+                    let budget: String = match stats.budget() {
+                        Some(budget) => format!(", budget {:.2} ms{}", ms(budget), if stats.is_over_budget() { " (OVER BUDGET)" } else { "" }),
+                        None         => String::new(),
+                    };
+                    info!("  - {}: avg {:.2} ms, max {:.2} ms{}", name, ms(stats.average()), ms(stats.max()), budget);
+                }
+            }
+        }
+    }
+
+    /// Records the time a system took in this frame.
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the system.
+    /// - `time`: The time the system took in this frame (summed over all its runs, e.g. for fixed updates).
+    pub(crate) fn record_system(&mut self, name: &'static str, time: Duration) {
+        self.systems.entry(name).or_default().record(name, time);
+    }
+
+    /// Sets the time a system may take per frame. Whenever it takes longer, a warning is logged (at most once per second).
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the system, as given when it was registered with the EventSystem.
+    /// - `budget`: The time the system may take per frame, or None to remove its budget.
+    #[inline]
+    pub fn set_budget(&mut self, name: &'static str, budget: Option<Duration>) {
+        self.systems.entry(name).or_default().budget = budget;
+    }
+
+    /// Enables or disables logging the statistics every second.
+    #[inline]
+    pub fn set_reporting(&mut self, enabled: bool) {
+        self.report       = enabled;
+        self.since_report = Duration::ZERO;
+    }
+
+
+
+    /// Returns the time the most recent frame took, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn last(&self) -> Duration { self.samples.back().copied().unwrap_or_default() }
+
+    /// Returns the average frame time over the most recent frames, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn average(&self) -> Duration {
+        if self.samples.is_empty() { return Duration::ZERO; }
+        self.total / self.samples.len() as u32
+    }
+
+    /// Returns the shortest frame time over the most recent frames, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn min(&self) -> Duration { self.samples.iter().min().copied().unwrap_or_default() }
+
+    /// Returns the longest frame time over the most recent frames, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn max(&self) -> Duration { self.samples.iter().max().copied().unwrap_or_default() }
+
+    /// Returns the statistics of the system with the given name, if it has run or has a budget.
+    #[inline]
+    pub fn system(&self, name: &str) -> Option<&SystemStats> { self.systems.get(name) }
+
+    /// Returns the statistics of all systems that have run or have a budget, ordered by name.
+    #[inline]
+    pub fn systems(&self) -> impl Iterator<Item = (&'static str, &SystemStats)> { self.systems.iter().map(|(name, stats)| (*name, stats)) }
+
+    /// Returns the average number of frames per second over the most recent frames, or zero if no frames were recorded yet.
+    #[inline]
+    pub fn fps(&self) -> f64 {
+        let average: f64 = self.average().as_secs_f64();
+        if average > 0.0 { 1.0 / average } else { 0.0 }
+    }
+}
+
@@ -4,7 +4,7 @@
 //  Created:
 //    18 Jul 2022, 18:27:38
 //  Last edited:
-//    07 Aug 2022, 18:56:15
+//    17 Oct 2026, 05:48:35
 //  Auto updated?
 //    Yes
 // 
@@ -15,17 +15,45 @@
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 use log::{debug, info, error};
 use rust_ecs::Ecs;
 use winit::event::{Event as WinitEvent, WindowEvent as WinitWindowEvent};
 use winit::event_loop::{ControlFlow, EventLoop};
+use winit::platform::run_return::EventLoopExtRunReturn;
 use winit::window::WindowId;
 
 use game_gfx::RenderSystem;
+use game_gfx::spec::WindowId as GfxWindowId;
 
 pub use crate::errors::EventError as Error;
-use crate::spec::Event;
+use crate::bus::{MessageBus, SubscriptionId};
+use crate::input::Input;
+use crate::spec::{BudgetsChanged, Event, IdleEvent, InputEvent, Propagation, WindowResized};
+use crate::time::Time;
+
+
+/***** TYPES *****/
+/// The callbacks that may be registered to run once per game tick. They receive the Time of the tick, the MessageBus (with this iteration's messages and input) and the RenderSystem.
+pub type TickCallback = Box<dyn FnMut(&Time, &MessageBus, &mut RenderSystem)>;
+
+/// The callbacks that may be registered to run once per fixed update. They receive the Time of the tick (whose `Time::fixed_step()` they should advance by) and the MessageBus.
+pub type FixedCallback = Box<dyn FnMut(&Time, &MessageBus)>;
+
+
+
+/// Groups the callbacks that the EventSystem runs during a game tick.
+#[derive(Default)]
+struct Callbacks {
+    /// The callbacks that run once per fixed update, with their names.
+    fixed : Vec<(&'static str, FixedCallback)>,
+    /// The callbacks that run once per game tick, with their names.
+    ticks : Vec<(&'static str, TickCallback)>,
+}
+
+
+
 
 
 /***** LIBRARY *****/
@@ -33,6 +61,17 @@ use crate::spec::Event;
 pub struct EventSystem {
     /// The ECS that the EventSystem may use for Events.
     ecs : Rc<RefCell<Ecs>>,
+    /// The MessageBus that systems use to talk to each other.
+    bus       : Rc<RefCell<MessageBus>>,
+    /// The Input that collects keyboard and mouse events for the next game tick.
+    input     : Rc<RefCell<Input>>,
+    /// The Time that tracks the simulation time per tick.
+    time      : Rc<RefCell<Time>>,
+    /// The callbacks that run during a game tick.
+    callbacks : Callbacks,
+    /// The Tracy client that receives frame marks and zones.
+    #[cfg(feature = "tracy")]
+    _tracy : tracy_client::Client,
 
     /// The EventLoop around which this EventSystem wraps.
     event_loop    : EventLoop<Event>,
@@ -51,6 +90,12 @@ impl EventSystem {
         // Return a new instance with that ECS, done
         Self {
             ecs,
+            bus       : Rc::new(RefCell::new(MessageBus::new())),
+            input     : Rc::new(RefCell::new(Input::new())),
+            time      : Rc::new(RefCell::new(Time::default())),
+            callbacks : Callbacks::default(),
+            #[cfg(feature = "tracy")]
+            _tracy : tracy_client::Client::start(),
 
             event_loop : EventLoop::with_user_event(),
         }
@@ -76,7 +121,7 @@ impl EventSystem {
             Event::WindowDraw(id) => Self::handle_window_draw(render_system, id),
 
             Event::GameLoopComplete => Self::handle_game_loop_complete(render_system),
-            Event::Exit(err)        => { Self::handle_exit(err); Ok(()) },
+            Event::Exit(err)        => { Self::handle_exit(err.as_ref()); Ok(()) },
         }
     }
 
@@ -129,9 +174,160 @@ impl EventSystem {
     /// 
     /// # Errors
     /// This function does not explicitly return errors. Instead, it logs them (using `error!()`), and fires the remaining close events as if the exit was called with an Error (overwriting any Error already set).
-    pub fn handle_exit(error: Option<Error>) {
+    pub fn handle_exit(error: Option<&Error>) {
         info!("Triggered Exit event");
-        if let Some(err) = error.as_ref() { debug!("Exit was triggered due to an error: {}", err); }
+        if let Some(err) = error { debug!("Exit was triggered due to an error: {}", err); }
+    }
+
+
+
+    /// Handles a single winit event, triggering the appropriate callbacks.
+    /// 
+    /// This is the part shared between `EventSystem::game_loop()` and `EventSystem::pump()`.
+    /// 
+    /// # Arguments
+    /// - `wevent`: The winit Event that occurred.
+    /// - `render_system`: The RenderSystem that processes any render-related events.
+    /// - `bus`: The MessageBus to publish window messages on, and to flush once all window events of this iteration have been handled.
+    /// - `input`: The Input that collects keyboard and mouse events, which are delivered just before the game tick.
+    /// - `time`: The Time to advance once per game tick (after applying any debug controls from this iteration's input).
+    /// - `callbacks`: The callbacks to run during the game tick, after the MessageBus has been flushed: first the fixed updates, then the tick callbacks.
+    /// - `control_flow`: The ControlFlow of the EventLoop, which is set to `ControlFlow::Exit` if the game should quit.
+    /// 
+    /// # Errors
+    /// Any error that occurs is printed to stderr using `log`'s `error!()` macro, after which the game is quit and the error is returned.
+    fn handle_winit_event(wevent: WinitEvent<Event>, render_system: &mut RenderSystem, bus: &RefCell<MessageBus>, input: &RefCell<Input>, time: &RefCell<Time>, callbacks: &mut Callbacks, control_flow: &mut ControlFlow) -> Result<(), Error> {
+        // Switch on the Event that happened
+        match wevent {
+            WinitEvent::WindowEvent{ window_id, event } => {
+                // Collect any input for the next tick
+                input.borrow_mut().record(window_id, &event);
+
+                // Match the event again
+                match event {
+                    WinitWindowEvent::CloseRequested => {
+                        // Closing a tool window only closes that window
+                        if let Some(id @ GfxWindowId::Tool(_)) = render_system.window_id(window_id) {
+                            if let Err(err) = render_system.close_window(id) {
+                                error!("Could not close window '{}': {}", id, err);
+                            }
+                            return Ok(());
+                        }
+
+                        // We close the flow in principle
+                        *control_flow = ControlFlow::Exit;
+
+                        // Fire close events (it acts as a sink for errors)
+                        Self::handle_exit(None);
+
+                        // Done
+                    },
+
+                    WinitWindowEvent::Focused(focused) => {
+                        // Keep track of whether we're in the background
+                        time.borrow_mut().idle_mut().set_focused(window_id, focused);
+                    },
+                    WinitWindowEvent::Destroyed => {
+                        // A closed window cannot keep the focus
+                        time.borrow_mut().idle_mut().set_focused(window_id, false);
+                    },
+
+                    WinitWindowEvent::Resized(size) => {
+                        // Let the other systems know
+                        bus.borrow().publish(WindowResized{ window: window_id, width: size.width, height: size.height });
+                    },
+
+                    // Ignore the others
+                    _ => {}
+                }
+            },
+
+            WinitEvent::MainEventsCleared => {
+                #[cfg(feature = "tracy")]
+                let _zone = tracy_client::span!("game_loop_complete");
+
+                // Trim resources if we became idle, and let the other systems know either way (before the flush, so they see it this tick)
+                if let Some(idle) = time.borrow_mut().idle_mut().update() {
+                    if idle { render_system.trim(); }
+                    bus.borrow().publish(if idle { IdleEvent::Entered } else { IdleEvent::Left });
+                }
+
+                // Deliver the input and the messages that systems published during this iteration
+                {
+                    let mut bus = bus.borrow_mut();
+                    input.borrow_mut().deliver(&mut bus);
+                    bus.flush();
+
+                    // Let the RenderSystem re-apply its budgets if they changed
+                    if let Some(changed) = bus.read::<BudgetsChanged>().last() { render_system.set_budgets(changed.render.clone()); }
+                }
+
+                // Advance the time, then run the fixed updates to catch up with it and the tick callbacks
+                {
+                    let bus = bus.borrow();
+                    let mut time = time.borrow_mut();
+                    for event in bus.read::<InputEvent>() {
+                        time.handle_debug_input(event);
+                    }
+                    time.advance();
+
+                    // Each callback is timed, so its statistics can be checked against its budget
+                    let mut spent: Vec<Duration> = vec![Duration::ZERO; callbacks.fixed.len()];
+                    for _ in 0..time.fixed_updates() {
+                        for ((_, fixed), spent) in callbacks.fixed.iter_mut().zip(spent.iter_mut()) {
+                            let start: Instant = Instant::now();
+                            fixed(&time, &bus);
+                            *spent += start.elapsed();
+                        }
+                    }
+                    for ((name, _), spent) in callbacks.fixed.iter().zip(spent) {
+                        time.stats_mut().record_system(name, spent);
+                    }
+                    render_system.set_interpolation(time.alpha());
+
+                    for (name, tick) in callbacks.ticks.iter_mut() {
+                        let start: Instant = Instant::now();
+                        tick(&time, &bus, render_system);
+                        time.stats_mut().record_system(name, start.elapsed());
+                    }
+                }
+
+                // Trigger the associated events
+                if let Err(err) = Self::handle_game_loop_complete(render_system) {
+                    // Print it, then quit the game
+                    error!("{}", &err);
+                    Self::handle_exit(Some(&err));
+                    *control_flow = ControlFlow::Exit;
+                    return Err(err);
+                }
+            },
+
+            WinitEvent::RedrawRequested(window_id) => {
+                #[cfg(feature = "tracy")]
+                let _zone = tracy_client::span!("render_window");
+
+                // Trigger the associated events
+                if let Err(err) = Self::handle_window_draw(render_system, window_id) {
+                    // Print it, then quit the game
+                    error!("{}", &err);
+                    Self::handle_exit(Some(&err));
+                    *control_flow = ControlFlow::Exit;
+                    return Err(err);
+                }
+            }
+
+            #[cfg(feature = "tracy")]
+            WinitEvent::RedrawEventsCleared => {
+                // All windows have been drawn, so this frame is done
+                tracy_client::frame_mark();
+            },
+
+            // Skip the rest (for now)
+            _ => {},
+        }
+
+        // Done
+        Ok(())
     }
 
 
@@ -148,57 +344,113 @@ impl EventSystem {
     /// Any error that occurs is printed to stderr using `log`'s `error!()` macro.
     pub fn game_loop(self, render_system: RenderSystem) -> ! {
         // Split self
-        let Self{ ecs: _ecs, event_loop } = self;
+        let Self{ ecs: _ecs, bus, input, time, mut callbacks, event_loop, .. } = self;
         let mut render_system = render_system;
 
         // Start the EventLoop
         event_loop.run(move |wevent, _, control_flow| {
-            // Switch on the Event that happened
-            match wevent {
-                WinitEvent::WindowEvent{ window_id: _window_id, event } => {
-                    // Match the event again
-                    match event {
-                        WinitWindowEvent::CloseRequested => {
-                            // We close the flow in principle
-                            *control_flow = ControlFlow::Exit;
-
-                            // Fire close events (it acts as a sink for errors)
-                            Self::handle_exit(None);
-
-                            // Done
-                        },
-    
-                        // Ignore the others
-                        _ => {}
-                    }
-                },
-
-                WinitEvent::MainEventsCleared => {
-                    // Trigger the associated events
-                    if let Err(err) = Self::handle_game_loop_complete(&render_system) {
-                        // Print it, then quit the game
-                        error!("{}", &err);
-                        Self::handle_exit(Some(err));
-                        *control_flow = ControlFlow::Exit;
-                    }
-                },
-
-                WinitEvent::RedrawRequested(window_id) => {
-                    // Trigger the associated events
-                    if let Err(err) = Self::handle_window_draw(&mut render_system, window_id) {
-                        // Print it, then quit the game
-                        error!("{}", &err);
-                        Self::handle_exit(Some(err));
-                        *control_flow = ControlFlow::Exit;
-                    }
-                }
-
-                // Skip the rest (for now)
-                _ => {},
+            let tick_done: bool = matches!(wevent, WinitEvent::MainEventsCleared);
+            // Errors have already been logged, and quit the game
+            let _ = Self::handle_winit_event(wevent, &mut render_system, &bus, &input, &time, &mut callbacks, control_flow);
+
+            // Render less often while idle
+            if tick_done && *control_flow != ControlFlow::Exit {
+                *control_flow = match time.borrow().idle().next_frame() {
+                    Some(next) => ControlFlow::WaitUntil(next),
+                    None       => ControlFlow::Poll,
+                };
             }
         })
     }
 
+    /// Runs a single iteration of the game loop, for when the EventSystem is embedded in a host application that owns the loop (e.g., an editor or a test).
+    /// 
+    /// Processes all pending events and renders one frame, then returns control to the caller. This is an alternative to `EventSystem::game_loop()`; don't mix the two.
+    /// 
+    /// Unlike `EventSystem::game_loop()`, this does not slow down while the game is idle; the host can check `Idle::next_frame()` on the Time to do so.
+    /// 
+    /// # Arguments
+    /// - `render_system`: The RenderSystem that processes any render-related events.
+    /// 
+    /// # Returns
+    /// Whether the game is still running. Once this returns false (e.g., because the window was closed), the host should stop calling `pump()` and drop the EventSystem and RenderSystem.
+    /// 
+    /// # Errors
+    /// This function errors if rendering failed. The error is also printed using `log`'s `error!()` macro, and the game should be treated as stopped.
+    pub fn pump(&mut self, render_system: &mut RenderSystem) -> Result<bool, Error> {
+        let mut running: bool          = true;
+        let mut error  : Option<Error> = None;
+        let bus       : &RefCell<MessageBus> = &self.bus;
+        let input     : &RefCell<Input>      = &self.input;
+        let time      : &RefCell<Time>       = &self.time;
+        let callbacks : &mut Callbacks       = &mut self.callbacks;
+        self.event_loop.run_return(|wevent, _, control_flow| {
+            // Never block the host while waiting for events
+            *control_flow = ControlFlow::Poll;
+
+            // Handle the event itself
+            let frame_done: bool = matches!(wevent, WinitEvent::RedrawEventsCleared);
+            if let Err(err) = Self::handle_winit_event(wevent, render_system, bus, input, time, callbacks, control_flow) {
+                if error.is_none() { error = Some(err); }
+            }
+
+            // If the handler wants to quit, the game is done; otherwise, return to the host once a full frame has been processed
+            if *control_flow == ControlFlow::Exit {
+                running = false;
+            } else if frame_done {
+                *control_flow = ControlFlow::Exit;
+            }
+        });
+        match error {
+            Some(err) => Err(err),
+            None      => Ok(running),
+        }
+    }
+
+
+
+    /// Registers a callback that runs once per game tick, just after the input and messages of this iteration have been delivered on the MessageBus.
+    /// 
+    /// Callbacks run in the order in which they were registered. They cannot fail; log any errors instead. Each callback is timed; give it a budget with `FrameStats::set_budget()` to be warned when it runs too long.
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the callback in the frame statistics. Should be unique among all tick and fixed update callbacks.
+    /// - `callback`: The callback to run. It receives the Time (whose `Time::delta()` it should advance by), the MessageBus (to read this iteration's messages, and to publish messages for the next one) and the RenderSystem.
+    pub fn on_tick<F: 'static + FnMut(&Time, &MessageBus, &mut RenderSystem)>(&mut self, name: &'static str, callback: F) {
+        self.callbacks.ticks.push((name, Box::new(callback)));
+    }
+
+    /// Registers a callback that runs once per fixed update, which happen at the rate set with `Time::set_fixed_step()` regardless of the frame rate.
+    /// 
+    /// A game tick runs as many fixed updates as needed to catch up with the simulation time (see `Time::fixed_updates()`), before any of the tick callbacks. Callbacks run in the order in which they were registered. They cannot fail; log any errors instead. Each callback is timed over all fixed updates in a frame; give it a budget with `FrameStats::set_budget()` to be warned when it runs too long.
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the callback in the frame statistics (e.g., "physics"). Should be unique among all tick and fixed update callbacks.
+    /// - `callback`: The callback to run. It receives the Time (whose `Time::fixed_step()` it should advance by) and the MessageBus (to read this iteration's messages, and to publish messages for the next one).
+    pub fn on_fixed_update<F: 'static + FnMut(&Time, &MessageBus)>(&mut self, name: &'static str, callback: F) {
+        self.callbacks.fixed.push((name, Box::new(callback)));
+    }
+
+
+
+    /// Subscribes a handler to messages of the given type on the MessageBus (see `MessageBus::subscribe()`).
+    /// 
+    /// Handlers run when the MessageBus is flushed, before the game tick, in order of decreasing priority. A handler can consume a message (e.g., a UI layer consuming a click on one of its widgets) so that lower-priority handlers and the tick callbacks don't see it.
+    /// 
+    /// # Generic types
+    /// - `M`: The type of the messages to handle (e.g., `InputEvent` or `WindowResized`).
+    /// 
+    /// # Arguments
+    /// - `priority`: The priority of the handler. Higher priorities see messages first.
+    /// - `handler`: The handler to call. Returning `Propagation::Consume` hides the message from the remaining handlers and from readers.
+    /// 
+    /// # Returns
+    /// A SubscriptionId that can be passed to `MessageBus::unsubscribe()`.
+    #[inline]
+    pub fn subscribe<M: 'static, F: 'static + FnMut(&M) -> Propagation>(&mut self, priority: i32, handler: F) -> SubscriptionId {
+        self.bus.borrow_mut().subscribe(priority, handler)
+    }
+
 
 
     /// Returns the name of the EventSystem, for use in Vulkan's AppInfo.
@@ -209,6 +461,22 @@ impl EventSystem {
     #[inline]
     pub fn version() -> &'static str { env!("CARGO_PKG_VERSION") }
 
+    /// Returns the MessageBus that systems can use to publish and read messages.
+    #[inline]
+    pub fn bus(&self) -> &Rc<RefCell<MessageBus>> { &self.bus }
+
+    /// Returns the Input that collects keyboard and mouse events, which can be used to register input callbacks or query the InputState.
+    /// 
+    /// Note that input callbacks are called while the MessageBus is borrowed, so they should not access it themselves.
+    #[inline]
+    pub fn input(&self) -> &Rc<RefCell<Input>> { &self.input }
+
+    /// Returns the Time that tracks the simulation time per tick, which can be used to pause, step or scale it.
+    /// 
+    /// Note that the Time is borrowed while the tick callbacks run; use the reference they are given instead.
+    #[inline]
+    pub fn time(&self) -> &Rc<RefCell<Time>> { &self.time }
+
     /// Returns the internal EventLoop.
     #[inline]
     pub fn event_loop(&self) -> &EventLoop<Event> { &self.event_loop }
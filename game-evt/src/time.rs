@@ -0,0 +1,303 @@
+//  TIME.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:06:19
+//  Last edited:
+//    17 Oct 2026, 05:46:29
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the Time resource, which tracks how much simulation time
+//!   passes per game tick and allows pausing, stepping and scaling it.
+// 
+
+use std::time::{Duration, Instant};
+
+use log::{debug, info};
+use winit::event::VirtualKeyCode;
+
+use crate::idle::Idle;
+use crate::spec::InputEvent;
+use crate::stats::FrameStats;
+
+
+/***** CONSTANTS *****/
+/// The time scales that the debug controls cycle through.
+const SCALES: [f32; 7] = [ 0.1, 0.25, 0.5, 1.0, 2.0, 3.0, 4.0 ];
+
+/// The smallest time scale that may be set.
+pub const MIN_SCALE: f32 = 0.1;
+/// The largest time scale that may be set.
+pub const MAX_SCALE: f32 = 4.0;
+
+/// The maximum number of fixed updates per tick. If the game falls further behind, the remaining time is dropped rather than caught up on.
+pub const MAX_FIXED_UPDATES: u32 = 8;
+
+
+
+
+
+/***** LIBRARY *****/
+/// Tracks the simulation time of the game.
+/// 
+/// Systems should use `Time::delta()` to advance their state, so they respect pausing, stepping and slow-motion. Systems that need a deterministic step (e.g., physics) should run as fixed updates instead, which advance by `Time::fixed_step()` at a time.
+#[derive(Clone, Debug)]
+pub struct Time {
+    /// The moment the previous tick started.
+    last       : Instant,
+    /// The real (wall-clock) time between the previous tick and this one.
+    real_delta : Duration,
+    /// The simulation time that passes in this tick.
+    delta      : Duration,
+    /// The total simulation time since the game started.
+    elapsed    : Duration,
+    /// The number of ticks so far.
+    tick       : u64,
+    /// The statistics over the real frame times.
+    stats      : FrameStats,
+    /// Tracks whether the game is idle in the background.
+    idle       : Idle,
+
+    /// The simulation time that is not yet consumed by fixed updates.
+    accumulator   : Duration,
+    /// The number of fixed updates to run in this tick.
+    fixed_updates : u32,
+    /// How far the simulation is between the previous fixed update and the next one, as a fraction of `fixed_step`.
+    alpha         : f32,
+
+    /// The size of a single fixed update, which is also the size of a single step while paused.
+    fixed_step   : Duration,
+    /// The factor with which real time is scaled to simulation time.
+    scale        : f32,
+    /// Whether the simulation is paused.
+    paused       : bool,
+    /// Whether a single step was requested while paused.
+    step_pending : bool,
+    /// Whether the debug keys control the time.
+    controls     : bool,
+}
+
+impl Time {
+    /// Constructor for the Time.
+    /// 
+    /// # Arguments
+    /// - `fixed_step`: The simulation time that passes in a single fixed update (and thus for a single `Time::step()` while paused).
+    /// 
+    /// # Returns
+    /// A new Time that starts running at normal speed.
+    /// 
+    /// # Panics
+    /// This function panics if `fixed_step` is zero.
+    pub fn new(fixed_step: Duration) -> Self {
+        if fixed_step.is_zero() { panic!("The fixed step cannot be zero"); }
+        Self {
+            last       : Instant::now(),
+            real_delta : Duration::ZERO,
+            delta      : Duration::ZERO,
+            elapsed    : Duration::ZERO,
+            tick       : 0,
+            stats      : FrameStats::new(),
+            idle       : Idle::default(),
+
+            accumulator   : Duration::ZERO,
+            fixed_updates : 0,
+            alpha         : 0.0,
+
+            fixed_step,
+            scale        : 1.0,
+            paused       : false,
+            step_pending : false,
+            controls     : false,
+        }
+    }
+
+
+
+    /// Starts a new tick, computing how much simulation time passes in it and how many fixed updates that amounts to.
+    pub(crate) fn advance(&mut self) {
+        let now: Instant = Instant::now();
+        self.real_delta = now - self.last;
+        self.last       = now;
+        // The first tick measures startup rather than a frame
+        if self.tick > 0 { self.stats.record(self.real_delta); }
+
+        self.delta = if !self.paused {
+            self.real_delta.mul_f32(self.scale)
+        } else if self.step_pending {
+            self.step_pending = false;
+            self.fixed_step
+        } else {
+            Duration::ZERO
+        };
+        self.elapsed += self.delta;
+        self.tick += 1;
+
+        // Consume the simulation time in whole fixed steps
+        self.accumulator += self.delta;
+        let steps: u128 = self.accumulator.as_nanos() / self.fixed_step.as_nanos();
+        if steps > MAX_FIXED_UPDATES as u128 {
+            debug!("Fixed updates are {} steps behind; skipping {} of them", steps, steps - MAX_FIXED_UPDATES as u128);
+            self.accumulator = Duration::from_nanos((self.accumulator.as_nanos() % self.fixed_step.as_nanos()) as u64);
+            self.fixed_updates = MAX_FIXED_UPDATES;
+        } else {
+            self.accumulator -= self.fixed_step * steps as u32;
+            self.fixed_updates = steps as u32;
+        }
+        self.alpha = self.accumulator.as_secs_f32() / self.fixed_step.as_secs_f32();
+    }
+
+    /// Applies the debug controls for the given input event, if they are enabled.
+    /// 
+    /// `P` toggles pause, `.` advances a single step while paused, and `-`/`=` slow down or speed up time.
+    /// 
+    /// # Arguments
+    /// - `event`: The InputEvent to react to.
+    pub(crate) fn handle_debug_input(&mut self, event: &InputEvent) {
+        if !self.controls { return; }
+        let key: VirtualKeyCode = match event {
+            InputEvent::KeyPressed(_, key) => *key,
+            _                              => { return; }
+        };
+        match key {
+            VirtualKeyCode::P      => { if self.paused { self.resume() } else { self.pause() } },
+            VirtualKeyCode::Period => { self.step(); },
+            VirtualKeyCode::Minus  => { self.set_scale(SCALES.iter().rev().copied().find(|s| *s < self.scale).unwrap_or(MIN_SCALE)); info!("Time scale set to {}x", self.scale); },
+            VirtualKeyCode::Equals => { self.set_scale(SCALES.iter().copied().find(|s| *s > self.scale).unwrap_or(MAX_SCALE)); info!("Time scale set to {}x", self.scale); },
+            _                      => {},
+        }
+    }
+
+
+
+    /// Pauses the simulation. Time no longer advances, except by `Time::step()`.
+    #[inline]
+    pub fn pause(&mut self) {
+        if !self.paused { info!("Simulation paused"); }
+        self.paused = true;
+    }
+
+    /// Resumes the simulation after a `Time::pause()`.
+    #[inline]
+    pub fn resume(&mut self) {
+        if self.paused { info!("Simulation resumed"); }
+        self.paused       = false;
+        self.step_pending = false;
+    }
+
+    /// Advances the simulation by exactly one fixed step in the next tick. Does nothing if the simulation isn't paused.
+    #[inline]
+    pub fn step(&mut self) {
+        if self.paused { self.step_pending = true; }
+    }
+
+    /// Enables or disables the debug keys that control the time (see `Time::handle_debug_input()`: `P` pauses, `.` steps, `-`/`=` scale time).
+    #[inline]
+    pub fn set_debug_controls(&mut self, enabled: bool) { self.controls = enabled; }
+
+    /// Sets the size of a single fixed update.
+    /// 
+    /// # Arguments
+    /// - `fixed_step`: The simulation time that passes in a single fixed update (e.g., `Duration::from_secs(1) / 60` for 60 Hz).
+    /// 
+    /// # Panics
+    /// This function panics if `fixed_step` is zero.
+    #[inline]
+    pub fn set_fixed_step(&mut self, fixed_step: Duration) {
+        if fixed_step.is_zero() { panic!("The fixed step cannot be zero"); }
+        self.fixed_step = fixed_step;
+    }
+
+    /// Sets the factor with which real time is scaled to simulation time.
+    /// 
+    /// # Arguments
+    /// - `scale`: The new time scale. It is clamped to `MIN_SCALE`-`MAX_SCALE`.
+    #[inline]
+    pub fn set_scale(&mut self, scale: f32) { self.scale = scale.clamp(MIN_SCALE, MAX_SCALE); }
+
+
+
+    /// Returns the simulation time that passes in this tick.
+    #[inline]
+    pub fn delta(&self) -> Duration { self.delta }
+
+    /// Returns the real (wall-clock) time between the previous tick and this one, regardless of pausing or scaling.
+    #[inline]
+    pub fn real_delta(&self) -> Duration { self.real_delta }
+
+    /// Returns the total simulation time since the game started.
+    #[inline]
+    pub fn elapsed(&self) -> Duration { self.elapsed }
+
+    /// Returns the number of ticks so far.
+    #[inline]
+    pub fn tick(&self) -> u64 { self.tick }
+
+    /// Returns the rolling statistics over the real frame times.
+    #[inline]
+    pub fn stats(&self) -> &FrameStats { &self.stats }
+
+    /// Returns the rolling statistics over the real frame times, mutably (e.g., to enable logging them).
+    #[inline]
+    pub fn stats_mut(&mut self) -> &mut FrameStats { &mut self.stats }
+
+    /// Returns the tracker that determines whether the game is idle in the background.
+    #[inline]
+    pub fn idle(&self) -> &Idle { &self.idle }
+
+    /// Returns the tracker that determines whether the game is idle in the background, mutably (e.g., to set its timeout).
+    #[inline]
+    pub fn idle_mut(&mut self) -> &mut Idle { &mut self.idle }
+
+    /// Returns the factor with which real time is scaled to simulation time.
+    #[inline]
+    pub fn scale(&self) -> f32 { self.scale }
+
+    /// Returns whether the simulation is paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool { self.paused }
+
+    /// Returns the simulation time that passes in a single fixed update (and for a single step while paused).
+    #[inline]
+    pub fn fixed_step(&self) -> Duration { self.fixed_step }
+
+    /// Returns the number of fixed updates that run in this tick.
+    #[inline]
+    pub fn fixed_updates(&self) -> u32 { self.fixed_updates }
+
+    /// Returns how far the simulation is between the previous fixed update and the next one, as a fraction in `[0, 1)`.
+    /// 
+    /// Rendering should interpolate between the previous and the current fixed state by this factor to hide the difference between the tick rate and the frame rate.
+    #[inline]
+    pub fn alpha(&self) -> f32 { self.alpha }
+}
+
+impl Default for Time {
+    /// Creates a Time with a fixed step of 1/60th of a second (i.e., 60 fixed updates per second).
+    #[inline]
+    fn default() -> Self { Self::new(Duration::from_secs(1) / 60) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    #[should_panic(expected = "The fixed step cannot be zero")]
+    fn test_new_rejects_zero_step() {
+        Time::new(Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic(expected = "The fixed step cannot be zero")]
+    fn test_set_fixed_step_rejects_zero_step() {
+        Time::default().set_fixed_step(Duration::ZERO);
+    }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 13:01:25
 //  Last edited:
-//    11 Aug 2022, 15:49:57
+//    17 Oct 2026, 05:18:45
 //  Auto updated?
 //    Yes
 // 
@@ -22,12 +22,16 @@ use std::fmt::{Display, Formatter, Result as FResult};
 pub enum RenderSystemError {
     /// Could not instantiate the Vulkan instance
     InstanceCreateError{ err: rust_vk::errors::InstanceError },
+    /// There are no supported GPUs to render on
+    NoSupportedGpu,
     /// Could not instantiate the Gpu
     DeviceCreateError{ err: rust_vk::errors::DeviceError },
     /// Could not create the CommandPool
     CommandPoolCreateError{ err: rust_vk::pools::errors::CommandPoolError },
     /// Could not create a new window
     WindowCreateError{ err: game_tgt::Error },
+    /// A window with the given ID is already open
+    DuplicateWindow{ id: crate::spec::WindowId },
     /// Could not initialize a new render pipeline.
     RenderPipelineCreateError{ name: &'static str, err: game_pip::Error },
     /// Failed to create a Semaphore
@@ -52,9 +56,11 @@ impl Display for RenderSystemError {
         use RenderSystemError::*;
         match self {
             InstanceCreateError{ err }             => write!(f, "Could not initialize graphics Instance: {}", err),
+            NoSupportedGpu                         => write!(f, "No supported GPU found (run 'game-list gpus' to see why)"),
             DeviceCreateError{ err }               => write!(f, "Could not initialize Device: {}", err),
             CommandPoolCreateError{ err }          => write!(f, "Could not initialize CommandPool: {}", err),
             WindowCreateError{ err }               => write!(f, "Could not initialize Window: {}", err),
+            DuplicateWindow{ id }                  => write!(f, "Window '{}' is already open", id),
             RenderPipelineCreateError{ name, err } => write!(f, "Could not initialize render pipeline '{}': {}", name, err),
             SemaphoreCreateError{ err }            => write!(f, "Failed to create Semaphore: {}", err),
             FenceCreateError{ err }                => write!(f, "Failed to create Fence: {}", err),
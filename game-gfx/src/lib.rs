@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 13:00:33
 //  Last edited:
-//    11 Aug 2022, 15:51:56
+//    17 Oct 2026, 05:05:18
 //  Auto updated?
 //    Yes
 // 
@@ -20,6 +20,8 @@
 pub mod errors;
 pub mod spec;
 pub mod components;
+pub mod registry;
+pub mod shaders;
 pub mod system;
 
 // Bring some components into the general package namespace
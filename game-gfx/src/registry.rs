@@ -0,0 +1,77 @@
+//  REGISTRY.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:47:20
+//  Last edited:
+//    17 Oct 2026, 04:47:20
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the ResourceRegistry, which is the central place where
+//!   the RenderSystem keeps its GPU resources behind typed handles.
+// 
+
+use std::rc::Rc;
+
+use rust_vk::image;
+use rust_vk::pools::memory::{IndexBuffer, VertexBuffer};
+
+use game_pip::spec::RenderPipeline;
+pub use game_utl::registry::{Handle, Registry};
+
+
+/***** LIBRARY *****/
+/// The ResourceRegistry, which keeps track of the RenderSystem's GPU resources.
+/// 
+/// Other systems (ECS components, pipelines) reference resources by Handle instead of owning them, so that they can be swapped or streamed in safely. Removed or replaced resources are kept alive until the frames in flight that may still use them have finished.
+pub struct ResourceRegistry {
+    /// The vertex buffers known to the RenderSystem.
+    pub vertex_buffers : Registry<Rc<VertexBuffer>>,
+    /// The index buffers known to the RenderSystem.
+    pub index_buffers  : Registry<Rc<IndexBuffer>>,
+    /// The textures (as image views) known to the RenderSystem.
+    pub textures       : Registry<Rc<image::View>>,
+    /// The render pipelines known to the RenderSystem.
+    pub pipelines      : Registry<Box<dyn RenderPipeline>>,
+}
+
+impl ResourceRegistry {
+    /// Constructor for the ResourceRegistry.
+    /// 
+    /// # Arguments
+    /// - `n_frames_in_flight`: The number of frames that may be in flight at once, which is how long removed resources are kept alive.
+    /// 
+    /// # Returns
+    /// A new, empty ResourceRegistry.
+    #[inline]
+    pub fn new(n_frames_in_flight: usize) -> Self {
+        Self {
+            vertex_buffers : Registry::new(n_frames_in_flight),
+            index_buffers  : Registry::new(n_frames_in_flight),
+            textures       : Registry::new(n_frames_in_flight),
+            pipelines      : Registry::new(n_frames_in_flight),
+        }
+    }
+
+
+
+    /// Marks the start of a new frame, dropping retired resources that can no longer be in use.
+    pub fn next_frame(&mut self) {
+        self.vertex_buffers.next_frame();
+        self.index_buffers.next_frame();
+        self.textures.next_frame();
+        self.pipelines.next_frame();
+    }
+
+    /// Drops all retired resources immediately.
+    /// 
+    /// Only call this after the Device has become idle.
+    pub fn flush(&mut self) {
+        self.vertex_buffers.flush();
+        self.index_buffers.flush();
+        self.textures.flush();
+        self.pipelines.flush();
+    }
+}
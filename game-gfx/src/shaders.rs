@@ -0,0 +1,118 @@
+//  SHADERS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:04:28
+//  Last edited:
+//    17 Oct 2026, 05:04:28
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements a watcher for the pipelines' compiled shaders, so they can
+//!   be reloaded when they change on disk.
+// 
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+use log::warn;
+
+use crate::spec::PipelineId;
+
+
+/***** CONSTANTS *****/
+/// The minimum time between two checks of the shader folders.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The pipelines whose shaders are watched.
+const PIPELINES: [PipelineId; 3] = [ PipelineId::Triangle, PipelineId::Square, PipelineId::TestPattern ];
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Returns the folder with the compiled shaders of the given pipeline.
+#[inline]
+fn shader_dir(id: PipelineId) -> &'static Path {
+    Path::new(match id {
+        PipelineId::Triangle    => game_pip::triangle::SHADER_DIR,
+        PipelineId::Square      => game_pip::square::SHADER_DIR,
+        PipelineId::TestPattern => game_pip::test_pattern::SHADER_DIR,
+    })
+}
+
+/// Returns the most recent modification time of the files in the given folder.
+/// 
+/// # Returns
+/// The latest modification time, or None if the folder could not be read or is empty.
+fn latest_change(dir: &Path) -> Option<SystemTime> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err)    => { warn!("Could not read shader folder '{}': {}", dir.display(), err); return None; }
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.metadata().ok())
+        .filter_map(|metadata| metadata.modified().ok())
+        .max()
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Watches the folders with the compiled shaders of the pipelines for changes.
+/// 
+/// This only makes sense in debug builds, where the shaders are read from those folders whenever a pipeline is created.
+pub struct ShaderWatcher {
+    /// The latest modification time we have seen per pipeline.
+    seen      : HashMap<PipelineId, Option<SystemTime>>,
+    /// The moment we last checked the folders.
+    last_poll : Instant,
+}
+
+impl ShaderWatcher {
+    /// Constructor for the ShaderWatcher, which remembers the current state of the shader folders.
+    /// 
+    /// # Returns
+    /// A new ShaderWatcher that reports changes made after this call.
+    pub fn new() -> Self {
+        Self {
+            seen      : PIPELINES.iter().map(|id| (*id, latest_change(shader_dir(*id)))).collect(),
+            last_poll : Instant::now(),
+        }
+    }
+
+
+
+    /// Checks which pipelines had their shaders changed since the previous call.
+    /// 
+    /// The folders are checked at most every half second; more frequent calls return nothing.
+    /// 
+    /// # Returns
+    /// The PipelineIds of the pipelines whose shaders changed.
+    pub fn poll(&mut self) -> Vec<PipelineId> {
+        if self.last_poll.elapsed() < POLL_INTERVAL { return vec![]; }
+        self.last_poll = Instant::now();
+
+        let mut changed: Vec<PipelineId> = vec![];
+        for (id, seen) in self.seen.iter_mut() {
+            let latest: Option<SystemTime> = latest_change(shader_dir(*id));
+            if latest > *seen {
+                *seen = latest;
+                changed.push(*id);
+            }
+        }
+        changed
+    }
+}
+
+impl Default for ShaderWatcher {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
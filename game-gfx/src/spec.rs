@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 13:01:17
 //  Last edited:
-//    11 Aug 2022, 15:50:36
+//    17 Oct 2026, 05:48:35
 //  Auto updated?
 //    Yes
 // 
@@ -15,6 +15,7 @@
 use std::fmt::{Display, Debug, Formatter, Result as FResult};
 use std::str::FromStr;
 
+use rust_vk::auxillary::structs::DeviceInfo;
 use semver::Version;
 
 
@@ -24,6 +25,8 @@ use semver::Version;
 pub enum WindowId {
     /// The main Window to which the RenderSystem renders.
     Main,
+    /// An additional Window for tools (e.g., a debug inspector), identified by a number of the caller's choosing.
+    Tool(usize),
 }
 
 impl Display for WindowId {
@@ -31,7 +34,33 @@ impl Display for WindowId {
     fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
         use WindowId::*;
         match self {
-            Main => write!(f, "Main"),
+            Main    => write!(f, "Main"),
+            Tool(i) => write!(f, "Tool {}", i),
+        }
+    }
+}
+
+
+
+/// Defines an ID to reference the pipelines that the RenderSystem can render a Window with.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub enum PipelineId {
+    /// The TrianglePipeline, which renders a simple triangle.
+    Triangle,
+    /// The SquarePipeline, which renders an indexed square.
+    Square,
+    /// The TestPatternPipeline, which renders a test card to validate the display setup.
+    TestPattern,
+}
+
+impl Display for PipelineId {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        use PipelineId::*;
+        match self {
+            Triangle    => write!(f, "Triangle"),
+            Square      => write!(f, "Square"),
+            TestPattern => write!(f, "TestPattern"),
         }
     }
 }
@@ -83,11 +112,60 @@ impl AppInfo {
 
 
 
+/// Selects the GPU to render on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GpuSelector {
+    /// The GPU with the given index, as listed by `RenderSystem::list_gpus()`. Note that indices may shift when drivers or hardware change.
+    Index(usize),
+    /// The first supported GPU whose name contains the given string (case-insensitive).
+    Name(String),
+}
+
+impl GpuSelector {
+    /// Checks whether the given GPU is selected by this GpuSelector.
+    /// 
+    /// # Arguments
+    /// - `info`: The DeviceInfo describing the GPU.
+    /// 
+    /// # Returns
+    /// True if it matches, false otherwise.
+    pub fn matches(&self, info: &DeviceInfo) -> bool {
+        match self {
+            GpuSelector::Index(index) => info.index == *index,
+            GpuSelector::Name(name)   => info.name.to_lowercase().contains(&name.to_lowercase()),
+        }
+    }
+}
+
+impl Display for GpuSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            GpuSelector::Index(index) => write!(f, "GPU {}", index),
+            GpuSelector::Name(name)   => write!(f, "GPU '{}'", name),
+        }
+    }
+}
+
+
+
 /// The VulkanInfo-struct defines information that is destined for the Vulkan backend.
 #[derive(Clone, Debug)]
 pub struct VulkanInfo {
-    /// The index of the GPU which we will use for rendering.
-    pub gpu   : usize,
+    /// The GPU which we will use for rendering.
+    pub gpu   : GpuSelector,
     /// If true, then we enable Vulkan debug layers.
     pub debug : bool,
 }
+
+
+
+/// The resource budgets that apply to rendering (a subset of the budgets of the quality presets in game-cfg).
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderBudgets {
+    /// The amount of VRAM that textures may use, in MiB.
+    pub texture_vram_mb   : u32,
+    /// The width and height of shadow maps, in pixels.
+    pub shadow_resolution : u32,
+    /// The distance (in world units) beyond which objects are not drawn.
+    pub draw_distance     : f32,
+}
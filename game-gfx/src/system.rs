@@ -4,7 +4,7 @@
 //  Created:
 //    26 Mar 2022, 18:07:31
 //  Last edited:
-//    20 Aug 2022, 13:43:30
+//    17 Oct 2026, 05:48:35
 //  Auto updated?
 //    Yes
 // 
@@ -12,11 +12,12 @@
 //!   Implements the base RenderSystem.
 // 
 
-use std::cell::{Ref, RefCell};
+use std::cell::{Ref, RefCell, RefMut};
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use log::debug;
+use log::{debug, error, info, warn};
 use rust_ecs::Ecs;
 use rust_vk::auxillary::enums::DeviceExtension;
 use rust_vk::auxillary::structs::{DeviceFeatures, DeviceInfo, MonitorInfo};
@@ -29,12 +30,15 @@ use semver::Version;
 use winit::event_loop::EventLoop;
 use winit::window::WindowId as WinitWindowId;
 
-use game_pip::SquarePipeline;
+use game_pip::{SquarePipeline, TestPatternPipeline, TrianglePipeline};
 use game_pip::spec::RenderPipeline;
+use game_tgt::RenderTarget;
 use game_tgt::window::WindowTarget;
 
 pub use crate::errors::RenderSystemError as Error;
-use crate::spec::{AppInfo, VulkanInfo, WindowId};
+use crate::spec::{AppInfo, GpuSelector, PipelineId, RenderBudgets, VulkanInfo, WindowId};
+use crate::registry::{Handle, ResourceRegistry};
+use crate::shaders::ShaderWatcher;
 
 
 /***** CONSTANTS *****/
@@ -50,6 +54,9 @@ const DEVICE_EXTENSIONS: &[&str] = &[ DeviceExtension::Swapchain.as_str() ];
 /// The list of device layers we want to enable.
 const DEVICE_LAYERS: &[&str] = &[];
 
+/// The number of frames that may be in flight at once.
+const N_FRAMES_IN_FLIGHT: usize = 3;
+
 // Constants that are lazily loaded
 lazy_static!{
     /// The list of device features we want to enable.
@@ -60,6 +67,42 @@ lazy_static!{
 
 
 
+/***** HELPER FUNCTIONS *****/
+/// Creates the RenderPipeline identified by the given PipelineId.
+/// 
+/// # Arguments
+/// - `id`: The PipelineId of the pipeline to create.
+/// - `device`: The Device where the pipeline will be created.
+/// - `memory_pool`: The MemoryPool where the pipeline may allocate its buffers.
+/// - `command_pool`: The CommandPool where the pipeline may allocate its command buffers.
+/// - `target`: The RenderTarget to which the pipeline will render.
+/// 
+/// # Returns
+/// The new pipeline, already boxed as a RenderPipeline.
+/// 
+/// # Errors
+/// This function errors if the pipeline itself failed to initialize.
+fn create_pipeline(id: PipelineId, device: &Rc<Device>, memory_pool: &Rc<RefCell<MetaPool>>, command_pool: &Rc<RefCell<CommandPool>>, target: Rc<RefCell<dyn RenderTarget>>) -> Result<Box<dyn RenderPipeline>, Error> {
+    match id {
+        PipelineId::Triangle => match TrianglePipeline::new(device.clone(), memory_pool.clone(), command_pool.clone(), target, N_FRAMES_IN_FLIGHT) {
+            Ok(pipeline) => Ok(Box::new(pipeline)),
+            Err(err)     => Err(Error::RenderPipelineCreateError{ name: "TrianglePipeline", err }),
+        },
+        PipelineId::Square => match SquarePipeline::new(device.clone(), memory_pool.clone(), command_pool.clone(), target, N_FRAMES_IN_FLIGHT) {
+            Ok(pipeline) => Ok(Box::new(pipeline)),
+            Err(err)     => Err(Error::RenderPipelineCreateError{ name: "SquarePipeline", err }),
+        },
+        PipelineId::TestPattern => match TestPatternPipeline::new(device.clone(), memory_pool.clone(), command_pool.clone(), target, N_FRAMES_IN_FLIGHT) {
+            Ok(pipeline) => Ok(Box::new(pipeline)),
+            Err(err)     => Err(Error::RenderPipelineCreateError{ name: "TestPatternPipeline", err }),
+        },
+    }
+}
+
+
+
+
+
 /***** LIBRARY *****/
 /// The RenderSystem, which handles the (rasterized) rendering & windowing part of the game.
 pub struct RenderSystem {
@@ -67,21 +110,38 @@ pub struct RenderSystem {
     _ecs : Rc<RefCell<Ecs>>,
 
     /// The Instance on which this RenderSystem is based.
-    _instance     : Rc<Instance>,
+    _instance    : Rc<Instance>,
+    /// The index of the GPU of the Device.
+    gpu          : usize,
+    /// The name of the GPU of the Device.
+    gpu_name     : String,
+    /// Whether the GPU is a fallback for a missing or unsupported one.
+    gpu_fallback : bool,
     /// The Device we'll use for rendering.
     device       : Rc<Device>,
     /// The CommandPool from which we allocate commands.
-    _command_pool : Rc<RefCell<CommandPool>>,
-    // /// The MemoryPool we use to allocate persistent buffers.
-    _memory_pool  : Rc<RefCell<MetaPool>>,
+    command_pool : Rc<RefCell<CommandPool>>,
+    /// The MemoryPool we use to allocate persistent buffers.
+    memory_pool  : Rc<RefCell<MetaPool>>,
     // /// The DescriptorPool from which we allocate descriptors.
+    /// The registry that owns the GPU resources (buffers, textures, pipelines) by handle.
+    resources    : Rc<RefCell<ResourceRegistry>>,
 
     /// A list of all Windows. These are also referenced in the targets map.
-    windows    : HashMap<WindowId, Rc<RefCell<WindowTarget>>>,
+    windows        : HashMap<WindowId, Rc<RefCell<WindowTarget>>>,
     /// Maps winit window IDs to our own semantic Window IDs.
-    window_ids : HashMap<WinitWindowId, WindowId>,
-    /// The map of render pipelines which we use to render to.
-    pipelines  : HashMap<WindowId, Box<dyn RenderPipeline>>,
+    window_ids     : HashMap<WinitWindowId, WindowId>,
+    /// The map of render pipelines (in the resource registry) which we use to render to.
+    pipelines      : HashMap<WindowId, Handle<Box<dyn RenderPipeline>>>,
+    /// The kind of pipeline that renders each Window.
+    pipeline_ids   : HashMap<WindowId, PipelineId>,
+    /// The watcher that reports changed shaders, if hot-reloading is enabled.
+    shader_watcher : Option<ShaderWatcher>,
+
+    /// How far the simulation is between its previous and next fixed update, as a fraction in `[0, 1)`.
+    interpolation : f32,
+    /// The resource budgets to stay within, if any have been set.
+    budgets       : Option<RenderBudgets>,
 }
 
 impl RenderSystem {
@@ -98,7 +158,7 @@ impl RenderSystem {
     /// - `ecs`: The ECS to register new components with.
     /// - `app_info`: The AppInfo struct that determines some application information.
     /// - `event_loop`: The EventLoop to use for triggering Window events and such.
-    /// - `gpu`: The index of the GPU to use for rendering.
+    /// - `gpu`: The GPU to use for rendering (part of `vulkan_info`), selected either by index or by (part of) its name.
     /// - `window_mode`: The WindowMode of the Window.
     /// - `debug`: If true, enables the validation layers in the Vulkan backend.
     /// 
@@ -132,8 +192,28 @@ impl RenderSystem {
             Err(err)     => { return Err(Error::InstanceCreateError{ err }); }  
         };
 
+        // Resolve the GPU to the index of a supported one, falling back to the best one if the selected GPU is missing or unsupported
+        let (supported, _) = match Device::list(instance.clone(), DEVICE_EXTENSIONS, DEVICE_LAYERS, &DEVICE_FEATURES) {
+            Ok(gpus) => gpus,
+            Err(err) => { return Err(Error::DeviceListError{ err }); }
+        };
+        let (gpu, gpu_name, gpu_fallback): (usize, String, bool) = match supported.iter().find(|info| vulkan_info.gpu.matches(info)) {
+            Some(info) => {
+                if let GpuSelector::Name(_) = &vulkan_info.gpu { info!("Selected GPU {} ('{}') for {}", info.index, info.name, vulkan_info.gpu); }
+                (info.index, info.name.clone(), false)
+            },
+            None => {
+                let fallback: &DeviceInfo = match supported.iter().max_by_key(|info| (info.kind.score(), Reverse(info.index))) {
+                    Some(info) => info,
+                    None       => { return Err(Error::NoSupportedGpu); }
+                };
+                warn!("{} is missing or unsupported; falling back to GPU {} ('{}')", vulkan_info.gpu, fallback.index, fallback.name);
+                (fallback.index, fallback.name.clone(), true)
+            },
+        };
+
         // Get the GPU
-        let device = match Device::new(instance.clone(), vulkan_info.gpu, DEVICE_EXTENSIONS, DEVICE_LAYERS, &*DEVICE_FEATURES) {
+        let device = match Device::new(instance.clone(), gpu, DEVICE_EXTENSIONS, DEVICE_LAYERS, &*DEVICE_FEATURES) {
             Ok(device) => device,
             Err(err)   => { return Err(Error::DeviceCreateError{ err }); }  
         };
@@ -161,15 +241,9 @@ impl RenderSystem {
         let window_ids : HashMap<WinitWindowId, WindowId>             = HashMap::from([ (main_window_id, WindowId::Main) ]);
 
         // Initiate the render pipelines
-        let mut pipelines: HashMap<WindowId, Box<dyn RenderPipeline>> = HashMap::with_capacity(1);
-        pipelines.insert(WindowId::Main, match SquarePipeline::new(device.clone(), memory_pool.clone(), command_pool.clone(), windows[&WindowId::Main].clone(), 3) {
-            Ok(pipeline) => Box::new(pipeline),
-            Err(err)     => { return Err(Error::RenderPipelineCreateError{ name: "SquarePipeline", err }); }
-        });
-        // pipelines.insert(WindowId::Main, match game_pip::triangle::Pipeline::new(device.clone(), memory_pool.clone(), command_pool.clone(), windows[&WindowId::Main].clone(), 3) {
-        //     Ok(pipeline) => Box::new(pipeline),
-        //     Err(err)     => { return Err(Error::RenderPipelineCreateError{ name: "TrianglePipeline", err }); }
-        // });
+        let mut resources: ResourceRegistry = ResourceRegistry::new(N_FRAMES_IN_FLIGHT);
+        let mut pipelines: HashMap<WindowId, Handle<Box<dyn RenderPipeline>>> = HashMap::with_capacity(1);
+        pipelines.insert(WindowId::Main, resources.pipelines.insert(create_pipeline(PipelineId::Square, &device, &memory_pool, &command_pool, windows[&WindowId::Main].clone())?));
 
 
 
@@ -178,14 +252,23 @@ impl RenderSystem {
         Ok(Self {
             _ecs : ecs,
 
-            _instance     : instance,
+            _instance    : instance,
+            gpu,
+            gpu_name,
+            gpu_fallback,
             device,
-            _command_pool : command_pool,
-            _memory_pool  : memory_pool,
+            command_pool,
+            memory_pool,
+            resources : Rc::new(RefCell::new(resources)),
 
             windows,
             window_ids,
             pipelines,
+            pipeline_ids   : HashMap::from([ (WindowId::Main, PipelineId::Square) ]),
+            shader_watcher : None,
+
+            interpolation : 0.0,
+            budgets       : None,
         })
     }
 
@@ -198,6 +281,9 @@ impl RenderSystem {
     /// # Returns
     /// Nothing, but does launch new callbacks in the Event system.
     pub fn game_loop_complete(&self) {
+        // Mark the start of a new frame in the registry
+        self.resources.borrow_mut().next_frame();
+
         // Go through all of the windows
         for window in self.windows.values() {
             // Get a borrow on it
@@ -228,9 +314,14 @@ impl RenderSystem {
         };
 
         // Resolve the window ID to a pipeline
-        let pipeline = match self.pipelines.get_mut(&window_id) {
+        let handle: Handle<Box<dyn RenderPipeline>> = match self.pipelines.get(window_id) {
+            Some(handle) => *handle,
+            None         => { panic!("Unknown window ID '{}'", window_id); }
+        };
+        let mut resources: RefMut<ResourceRegistry> = self.resources.borrow_mut();
+        let pipeline: &mut Box<dyn RenderPipeline> = match resources.pipelines.get_mut(handle) {
             Some(pipeline) => pipeline,
-            None           => { panic!("Unknown window ID '{}'", window_id); }
+            None           => { panic!("Pipeline for window ID '{}' is no longer registered", window_id); }
         };
 
         // This is the pipeline that we want to render
@@ -240,6 +331,175 @@ impl RenderSystem {
         }
     }
 
+    /// Replaces the RenderPipeline that renders to the given Window.
+    /// 
+    /// Waits until the Device is idle before the old pipeline is dropped, so its resources are no longer in use.
+    /// 
+    /// # Arguments
+    /// - `window_id`: The WindowId of the Window to render to.
+    /// - `pipeline_id`: The PipelineId of the pipeline to render the Window with.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to wait for the Device or if the new pipeline failed to initialize.
+    /// 
+    /// # Panics
+    /// This function panics if the given `window_id` does not exist.
+    pub fn set_pipeline(&mut self, window_id: WindowId, pipeline_id: PipelineId) -> Result<(), Error> {
+        // Resolve the window
+        let target: Rc<RefCell<WindowTarget>> = match self.windows.get(&window_id) {
+            Some(target) => target.clone(),
+            None         => { panic!("Unknown window ID '{}'", window_id); }
+        };
+
+        // Make sure the old pipeline is no longer in use
+        self.wait_for_idle()?;
+
+        // Create the new pipeline and swap it in the registry (which retires the old one)
+        debug!("Switching pipeline of window '{}' to {}", window_id, pipeline_id);
+        let pipeline: Box<dyn RenderPipeline> = create_pipeline(pipeline_id, &self.device, &self.memory_pool, &self.command_pool, target)?;
+        let mut resources: RefMut<ResourceRegistry> = self.resources.borrow_mut();
+        match self.pipelines.get(&window_id) {
+            Some(handle) if resources.pipelines.contains(*handle) => { resources.pipelines.replace(*handle, pipeline); },
+            _                                                     => { self.pipelines.insert(window_id, resources.pipelines.insert(pipeline)); },
+        }
+        self.pipeline_ids.insert(window_id, pipeline_id);
+        Ok(())
+    }
+
+    /// Opens an additional Window that the RenderSystem renders to alongside the main one.
+    /// 
+    /// # Arguments
+    /// - `event_loop`: The EventLoop to which the new Window will be bound. Must be the same as the one given to `RenderSystem::new()`.
+    /// - `window_id`: The WindowId under which the new Window will be known.
+    /// - `window_info`: The WindowInfo that describes the new Window.
+    /// - `pipeline_id`: The PipelineId of the pipeline to render the new Window with.
+    /// 
+    /// # Errors
+    /// This function errors if a Window with the given ID is already open, or if we failed to create the Window or its pipeline.
+    pub fn open_window<T>(&mut self, event_loop: &EventLoop<T>, window_id: WindowId, window_info: WindowInfo, pipeline_id: PipelineId) -> Result<(), Error> {
+        if self.windows.contains_key(&window_id) { return Err(Error::DuplicateWindow{ id: window_id }); }
+
+        // Build the window
+        let window: Rc<RefCell<WindowTarget>> = match WindowTarget::new(self.device.clone(), event_loop, window_info) {
+            Ok(window) => Rc::new(RefCell::new(window)),
+            Err(err)   => { return Err(Error::WindowCreateError{ err }); }
+        };
+        let winit_id: WinitWindowId = window.borrow().window().id();
+
+        // Create its pipeline
+        let pipeline: Box<dyn RenderPipeline> = create_pipeline(pipeline_id, &self.device, &self.memory_pool, &self.command_pool, window.clone())?;
+
+        // Register it
+        debug!("Opened window '{}' with {}", window_id, pipeline_id);
+        self.pipelines.insert(window_id, self.resources.borrow_mut().pipelines.insert(pipeline));
+        self.windows.insert(window_id, window);
+        self.window_ids.insert(winit_id, window_id);
+        self.pipeline_ids.insert(window_id, pipeline_id);
+        Ok(())
+    }
+
+    /// Closes an additional Window, destroying it and its pipeline.
+    /// 
+    /// Waits until the Device is idle first, so the Window's resources are no longer in use.
+    /// 
+    /// # Arguments
+    /// - `window_id`: The WindowId of the Window to close.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to wait for the Device.
+    /// 
+    /// # Panics
+    /// This function panics if the given `window_id` does not exist, or if it is `WindowId::Main` (which lives as long as the RenderSystem).
+    pub fn close_window(&mut self, window_id: WindowId) -> Result<(), Error> {
+        if window_id == WindowId::Main { panic!("Cannot close the main window"); }
+        if !self.windows.contains_key(&window_id) { panic!("Unknown window ID '{}'", window_id); }
+
+        // Make sure nothing of the window is in use anymore (if this fails, the window is left intact)
+        self.wait_for_idle()?;
+
+        // Remove it everywhere at once, then drop the pipeline (and with it, the window) immediately
+        debug!("Closing window '{}'", window_id);
+        let target: Rc<RefCell<WindowTarget>> = self.windows.remove(&window_id).unwrap();
+        self.window_ids.remove(&target.borrow().window().id());
+        self.pipeline_ids.remove(&window_id);
+        let mut resources: RefMut<ResourceRegistry> = self.resources.borrow_mut();
+        if let Some(handle) = self.pipelines.remove(&window_id) { resources.pipelines.remove(handle); }
+        resources.flush();
+        Ok(())
+    }
+
+    /// Starts watching the pipelines' compiled shaders, so `RenderSystem::reload_shaders()` can rebuild pipelines whose shaders changed.
+    /// 
+    /// This only has effect in debug builds, as release builds embed the shaders in the executable.
+    pub fn watch_shaders(&mut self) {
+        if !cfg!(debug_assertions) {
+            warn!("Shader hot-reloading is only supported in debug builds; shaders will not be reloaded");
+            return;
+        }
+        info!("Watching shaders for changes");
+        self.shader_watcher = Some(ShaderWatcher::new());
+    }
+
+    /// Rebuilds the pipelines whose shaders changed on disk since the last call.
+    /// 
+    /// Does nothing if `RenderSystem::watch_shaders()` has not been called. Errors are logged, and the affected Window keeps rendering with its old pipeline.
+    pub fn reload_shaders(&mut self) {
+        let changed: Vec<PipelineId> = match self.shader_watcher.as_mut() {
+            Some(watcher) => watcher.poll(),
+            None          => { return; }
+        };
+
+        for pipeline_id in changed {
+            let windows: Vec<WindowId> = self.pipeline_ids.iter().filter(|(_, id)| **id == pipeline_id).map(|(window, _)| *window).collect();
+            for window_id in windows {
+                info!("Shaders of the {} pipeline changed; rebuilding it for window '{}'", pipeline_id, window_id);
+                if let Err(err) = self.set_pipeline(window_id, pipeline_id) {
+                    error!("Could not reload the {} pipeline for window '{}': {}", pipeline_id, window_id, err);
+                }
+            }
+        }
+    }
+
+    /// Releases the GPU resources that the RenderSystem can do without for now, e.g. when the game is idle in the background.
+    /// 
+    /// Waits for the Device to become idle, and then drops all retired resources instead of waiting for the frames in flight to finish. Errors are logged.
+    pub fn trim(&mut self) {
+        debug!("Trimming GPU resources");
+        if let Err(err) = self.wait_for_idle() {
+            error!("Could not trim GPU resources: {}", err);
+            return;
+        }
+        self.resources.borrow_mut().flush();
+    }
+
+    /// Sets how far the simulation is between its previous and next fixed update, so that rendering can interpolate between them.
+    /// 
+    /// # Arguments
+    /// - `interpolation`: The interpolation factor, as a fraction in `[0, 1)` (see game-evt's `Time::alpha()`).
+    #[inline]
+    pub fn set_interpolation(&mut self, interpolation: f32) { self.interpolation = interpolation; }
+
+    /// Sets the resource budgets that rendering should stay within, e.g. after the quality preset changed.
+    /// 
+    /// If the texture budget shrinks, the resources that can be done without are released immediately (see `RenderSystem::trim()`).
+    /// 
+    /// # Arguments
+    /// - `budgets`: The new RenderBudgets.
+    pub fn set_budgets(&mut self, budgets: RenderBudgets) {
+        if self.budgets.as_ref() == Some(&budgets) { return; }
+        info!("Render budgets set to {} MiB of textures, {}px shadows and a draw distance of {}", budgets.texture_vram_mb, budgets.shadow_resolution, budgets.draw_distance);
+        let shrunk: bool = self.budgets.as_ref().map(|old| budgets.texture_vram_mb < old.texture_vram_mb).unwrap_or(false);
+        self.budgets = Some(budgets);
+        if shrunk { self.trim(); }
+    }
+
+    /// Resolves a winit window ID to the WindowId under which the RenderSystem knows it.
+    /// 
+    /// # Returns
+    /// The matching WindowId, or None if the window is not (or no longer) managed by the RenderSystem.
+    #[inline]
+    pub fn window_id(&self, window_id: WinitWindowId) -> Option<WindowId> { self.window_ids.get(&window_id).copied() }
+
     /// Blocks the current thread until the Device is idle
     #[inline]
     pub fn wait_for_idle(&self) -> Result<(), Error> {
@@ -251,6 +511,44 @@ impl RenderSystem {
 
 
 
+    /// Returns the index of the GPU that is used for rendering. This may differ from the selected GPU if that was missing or unsupported.
+    #[inline]
+    pub fn gpu(&self) -> usize { self.gpu }
+
+    /// Returns the name of the GPU that is used for rendering.
+    #[inline]
+    pub fn gpu_name(&self) -> &str { &self.gpu_name }
+
+    /// Returns whether the GPU that is used for rendering is a fallback, because the selected one was missing or unsupported.
+    #[inline]
+    pub fn is_gpu_fallback(&self) -> bool { self.gpu_fallback }
+
+    /// Returns the Device used for rendering.
+    #[inline]
+    pub fn device(&self) -> &Rc<Device> { &self.device }
+
+    /// Returns the MemoryPool from which persistent buffers are allocated.
+    #[inline]
+    pub fn memory_pool(&self) -> &Rc<RefCell<MetaPool>> { &self.memory_pool }
+
+    /// Returns the CommandPool from which command buffers are allocated.
+    #[inline]
+    pub fn command_pool(&self) -> &Rc<RefCell<CommandPool>> { &self.command_pool }
+
+    /// Returns the registry that owns the RenderSystem's GPU resources.
+    #[inline]
+    pub fn resources(&self) -> &Rc<RefCell<ResourceRegistry>> { &self.resources }
+
+    /// Returns how far the simulation is between its previous and next fixed update, as a fraction in `[0, 1)`.
+    #[inline]
+    pub fn interpolation(&self) -> f32 { self.interpolation }
+
+    /// Returns the resource budgets that rendering should stay within, or None if they have not been set.
+    #[inline]
+    pub fn budgets(&self) -> Option<&RenderBudgets> { self.budgets.as_ref() }
+
+
+
     /// Automatically selects the best GPU.
     /// 
     /// Creates a new instance with the proper layers and extensions, and then tries to find the GPU with the best "CPU disconnectedness".
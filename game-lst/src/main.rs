@@ -4,7 +4,7 @@
  * Created:
  *   16 Apr 2022, 13:01:51
  * Last edited:
- *   15 Jul 2022, 18:40:11
+ *   17 Oct 2026, 05:17:43
  * Auto updated?
  *   Yes
  *
@@ -117,7 +117,8 @@ fn main() {
             }
         
             println!();
-            println!("To use a GPU, edit settings.json and set 'gpu' to the index of the GPU you'd like to use.");
+            println!("To use a GPU, edit settings.json and set 'gpu' to the index of the GPU you'd like to use, or to (part of) its name as a string (e.g., \"gpu\": \"nvidia\").");
+            println!("Names stay valid when driver updates change the order of the GPUs.");
         
             // Done
             println!();
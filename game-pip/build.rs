@@ -4,7 +4,7 @@
 //  Created:
 //    30 Apr 2022, 17:52:26
 //  Last edited:
-//    20 Aug 2022, 14:30:07
+//    17 Oct 2026, 05:55:10
 //  Auto updated?
 //    Yes
 // 
@@ -103,6 +103,14 @@ fn main() {
                             let out: PathBuf = spirv_dir.join(&format!("{}.spv", entry_path.file_name().unwrap().to_string_lossy().to_string()));
                             println!("Compiling '{}' to '{}'...", entry_path.display(), out.display());
                             glslc!("-o", out, entry_path);
+
+                            // Shaders that check for an sRGB target get a second variant for those
+                            let source: String = fs::read_to_string(&entry_path).unwrap_or_else(|err| panic!("Could not read shader '{}': {}", entry_path.display(), err));
+                            if source.contains("SRGB_TARGET") {
+                                let out: PathBuf = spirv_dir.join(&format!("{}.srgb.spv", entry_path.file_name().unwrap().to_string_lossy().to_string()));
+                                println!("Compiling '{}' to '{}' (for sRGB targets)...", entry_path.display(), out.display());
+                                glslc!("-DSRGB_TARGET", "-o", out, entry_path);
+                            }
                         }
                     }
                 }
@@ -0,0 +1,205 @@
+//  BUFFERS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:14:39
+//  Last edited:
+//    17 Oct 2026, 05:52:44
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements helpers for creating the vertex and index buffers of a
+//!   pipeline, and for recording indexed draws with them.
+// 
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rust_vk::device::Device;
+use rust_vk::pools::memory::prelude::*;
+use rust_vk::pools::memory::{IndexBuffer, MappedMemory, StagingBuffer, VertexBuffer};
+use rust_vk::pools::command::{Buffer as CommandBuffer, Pool as CommandPool};
+
+pub use crate::errors::RenderPipelineError as Error;
+
+
+/***** INTERFACES *****/
+/// Defines the integer types that may be used as indices in an IndexBuffer.
+/// 
+/// rust-vk (v1.0.0) only knows how to allocate 32-bit index buffers, so narrower types are widened to `u32` before they are uploaded.
+pub trait IndexType: Copy {
+    /// Returns the given indices as 32-bit indices, copying them only if they aren't 32-bit already.
+    /// 
+    /// # Arguments
+    /// - `indices`: The indices to convert.
+    /// 
+    /// # Returns
+    /// The indices as `u32`s.
+    fn as_u32s(indices: &[Self]) -> Cow<'_, [u32]>;
+}
+
+impl IndexType for u16 {
+    #[inline]
+    fn as_u32s(indices: &[Self]) -> Cow<'_, [u32]> { Cow::Owned(indices.iter().map(|i| u32::from(*i)).collect()) }
+}
+
+impl IndexType for u32 {
+    #[inline]
+    fn as_u32s(indices: &[Self]) -> Cow<'_, [u32]> { Cow::Borrowed(indices) }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Populates the given device-local buffer with the given data through a temporary staging buffer.
+/// 
+/// # Arguments
+/// - `name`: The name of the pipeline for which we populate the buffer (used for debugging).
+/// - `what`: What kind of buffer this is (used for debugging).
+/// - `command_pool`: The CommandPool where we will get a command buffer to do the copy on.
+/// - `buffer`: The Buffer to populate.
+/// - `tbuffer`: The same Buffer, but as a TransferBuffer.
+/// - `data`: The data to write to the Buffer.
+fn populate<T: Clone>(name: &'static str, what: &'static str, command_pool: &Rc<RefCell<CommandPool>>, buffer: &Rc<dyn Buffer>, tbuffer: &Rc<dyn TransferBuffer>, data: &[T]) -> Result<(), Error> {
+    // Create the staging buffer
+    let staging: Rc<StagingBuffer> = match StagingBuffer::new_for(buffer) {
+        Ok(staging) => staging,
+        Err(err)    => { return Err(Error::BufferCreateError{ name, what: "staging", err }); }
+    };
+
+    // Populate the staging buffer
+    {
+        let mapped: MappedMemory = match staging.map() {
+            Ok(mapped) => mapped,
+            Err(err)   => { return Err(Error::BufferMapError{ name, what: "staging", err }); }
+        };
+        mapped.as_slice_mut::<T>(data.len()).clone_from_slice(data);
+        if let Err(err) = mapped.flush() { return Err(Error::BufferFlushError{ name, what: "staging", err }); }
+    }
+
+    // Copy the staging to the normal buffer
+    if let Err(err) = staging.copyto(command_pool, tbuffer) { return Err(Error::BufferCopyError{ name, src: "staging", dst: what, err }); }
+    Ok(())
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Creates, allocates and populates a vertex buffer with the given vertices.
+/// 
+/// # Arguments
+/// - `name`: The name of the pipeline for which we create the buffer (used for debugging).
+/// - `device`: The Device where the new Buffer will be allocated. Note that the Buffer's memory will be allocated on the device of the given `memory_pool`.
+/// - `memory_pool`: The MemoryPool where to allocate the memory for the vertex buffer (and a temporary staging buffer).
+/// - `command_pool`: The CommandPool where we will get a command buffer to do the copy on.
+/// - `vertices`: The vertices to upload.
+/// 
+/// # Returns
+/// A new VertexBuffer with the given vertices in device-local memory.
+/// 
+/// # Errors
+/// This function errors if we failed to allocate or populate the buffer.
+pub fn create_vertex_buffer<V: Vertex + Clone>(name: &'static str, device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>, vertices: &[V]) -> Result<Rc<VertexBuffer>, Error> {
+    // Create the Vertex buffer object
+    let buffer: Rc<VertexBuffer> = match VertexBuffer::new::<V>(device.clone(), memory_pool.clone(), vertices.len()) {
+        Ok(buffer) => buffer,
+        Err(err)   => { return Err(Error::BufferCreateError{ name, what: "vertex", err }); }
+    };
+
+    // Populate it
+    let bbuffer: Rc<dyn Buffer> = buffer.clone();
+    let tbuffer: Rc<dyn TransferBuffer> = buffer.clone();
+    populate(name, "vertex", command_pool, &bbuffer, &tbuffer, vertices)?;
+    Ok(buffer)
+}
+
+/// Creates, allocates and populates an index buffer with the given indices.
+/// 
+/// The indices may be given as either `u16` or `u32`; see `IndexType` for how they end up on the GPU.
+/// 
+/// # Arguments
+/// - `name`: The name of the pipeline for which we create the buffer (used for debugging).
+/// - `device`: The Device where the new Buffer will be allocated. Note that the Buffer's memory will be allocated on the device of the given `memory_pool`.
+/// - `memory_pool`: The MemoryPool where to allocate the memory for the index buffer (and a temporary staging buffer).
+/// - `command_pool`: The CommandPool where we will get a command buffer to do the copy on.
+/// - `indices`: The indices to upload.
+/// 
+/// # Returns
+/// A new IndexBuffer with the given indices in device-local memory.
+/// 
+/// # Errors
+/// This function errors if we failed to allocate or populate the buffer.
+pub fn create_index_buffer<I: IndexType>(name: &'static str, device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>, indices: &[I]) -> Result<Rc<IndexBuffer>, Error> {
+    let indices: Cow<[u32]> = I::as_u32s(indices);
+
+    // Create the Index buffer object
+    let buffer: Rc<IndexBuffer> = match IndexBuffer::new_u32(device.clone(), memory_pool.clone(), indices.len()) {
+        Ok(buffer) => buffer,
+        Err(err)   => { return Err(Error::BufferCreateError{ name, what: "index", err }); }
+    };
+
+    // Populate it
+    let bbuffer: Rc<dyn Buffer> = buffer.clone();
+    let tbuffer: Rc<dyn TransferBuffer> = buffer.clone();
+    populate(name, "index", command_pool, &bbuffer, &tbuffer, &indices)?;
+    Ok(buffer)
+}
+
+
+
+/// A vertex buffer and matching index buffer that can be drawn with a single indexed draw call.
+#[derive(Clone)]
+pub struct IndexedGeometry {
+    /// The buffer with the vertices.
+    pub vertices  : Rc<VertexBuffer>,
+    /// The buffer with the indices into `vertices`.
+    pub indices   : Rc<IndexBuffer>,
+    /// The number of indices in `indices`.
+    pub n_indices : u32,
+}
+
+impl IndexedGeometry {
+    /// Constructor for the IndexedGeometry, which uploads the given vertices and indices to the GPU.
+    /// 
+    /// # Arguments
+    /// - `name`: The name of the pipeline for which we create the geometry (used for debugging).
+    /// - `device`: The Device where the new Buffers will be allocated.
+    /// - `memory_pool`: The MemoryPool where to allocate the memory for the buffers (and temporary staging buffers).
+    /// - `command_pool`: The CommandPool where we will get a command buffer to do the copies on.
+    /// - `vertices`: The vertices to upload.
+    /// - `indices`: The indices to upload, three per triangle. May be either `u16` or `u32`.
+    /// 
+    /// # Returns
+    /// A new IndexedGeometry with both buffers in device-local memory.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to allocate or populate either buffer.
+    pub fn new<V: Vertex + Clone, I: IndexType>(name: &'static str, device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>, vertices: &[V], indices: &[I]) -> Result<Self, Error> {
+        Ok(Self {
+            vertices  : create_vertex_buffer(name, device, memory_pool, command_pool, vertices)?,
+            indices   : create_index_buffer(name, device, memory_pool, command_pool, indices)?,
+            n_indices : indices.len() as u32,
+        })
+    }
+
+
+
+    /// Records binding the buffers and drawing all of the geometry's indices once.
+    /// 
+    /// Must be called while the given CommandBuffer is recording within a render pass, and after a pipeline has been bound.
+    /// 
+    /// # Arguments
+    /// - `cmd`: The CommandBuffer to record the draw in.
+    #[inline]
+    pub fn record_draw(&self, cmd: &Rc<CommandBuffer>) {
+        cmd.bind_vertex_buffer(0, &self.vertices);
+        cmd.bind_index_buffer(&self.indices);
+        cmd.draw_indexed(self.n_indices, 1, 0, 0, 0);
+    }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    11 Aug 2022, 15:35:15
 //  Last edited:
-//    11 Aug 2022, 15:56:42
+//    17 Oct 2026, 05:02:54
 //  Auto updated?
 //    Yes
 // 
@@ -16,11 +16,15 @@
 // Declare submodules
 pub mod errors;
 pub mod spec;
+pub mod buffers;
 pub mod triangle;
 pub mod square;
+pub mod test_pattern;
 
 // Pull some stuff into the general namespace
 pub use errors::RenderPipelineError as Error;
 pub use spec::RenderPipeline;
+pub use buffers::IndexedGeometry;
 pub use triangle::{Pipeline as TrianglePipeline};
 pub use square::{Pipeline as SquarePipeline};
+pub use test_pattern::{Pipeline as TestPatternPipeline};
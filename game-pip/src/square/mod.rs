@@ -4,7 +4,7 @@
 //  Created:
 //    11 Aug 2022, 15:55:22
 //  Last edited:
-//    13 Aug 2022, 12:59:58
+//    17 Oct 2026, 05:05:18
 //  Auto updated?
 //    Yes
 // 
@@ -21,6 +21,8 @@ pub mod pipeline;
 // Define constants
 /// The name of this specific pipeline
 pub const NAME: &'static str = "Square";
+/// The folder with the compiled shaders of this pipeline. They are embedded in release builds, but read from here at runtime in debug builds.
+pub const SHADER_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/square/shaders/spir-v");
 
 
 // Load the shader files
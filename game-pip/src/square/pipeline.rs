@@ -4,7 +4,7 @@
 //  Created:
 //    11 Aug 2022, 15:58:03
 //  Last edited:
-//    20 Aug 2022, 14:43:17
+//    17 Oct 2026, 05:52:44
 //  Auto updated?
 //    Yes
 // 
@@ -25,7 +25,6 @@ use rust_vk::layout::PipelineLayout;
 use rust_vk::render_pass::{RenderPass, RenderPassBuilder};
 use rust_vk::pipeline::{Pipeline as VkPipeline, PipelineBuilder as VkPipelineBuilder};
 use rust_vk::pools::memory::prelude::*;
-use rust_vk::pools::memory::{IndexBuffer, MappedMemory, StagingBuffer, VertexBuffer};
 use rust_vk::pools::command::{Buffer as CommandBuffer, Pool as CommandPool};
 use rust_vk::image;
 use rust_vk::framebuffer::Framebuffer;
@@ -37,6 +36,7 @@ use super::{NAME, Shaders};
 use super::vertex::SquareVertex;
 
 pub use crate::errors::RenderPipelineError as Error;
+use crate::buffers::IndexedGeometry;
 use crate::spec::RenderPipeline;
 
 
@@ -62,97 +62,13 @@ const VERTICES: [SquareVertex; 4] = [
 ];
 
 /// The raw index data we'd like to send to the GPU.
-const INDICES: [u32; 6] = [0, 1, 2, 2, 3, 0];
+const INDICES: [u16; 6] = [0, 1, 2, 2, 3, 0];
 
 
 
 
 
 /***** HELPER FUNCTIONS *****/
-/// Creates, allocates and populates the vertex buffer.
-/// 
-/// # Arguments
-/// - `device`: The Device where the new Buffer will be allocated. Note that the Buffer's memory will be allocated on the device of the given `memory_pool`.
-/// - `memory_pool`: The MemoryPool where to allocate the memory for the vertex buffer (and a temporary staging buffer).
-/// - `command_pool`: The CommandPool where we will get a command buffer to do the copy on.
-fn create_vertex_buffer(device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>) -> Result<Rc<VertexBuffer>, Error> {
-    // Create the Vertex buffer object
-    let vertices: Rc<VertexBuffer> = match VertexBuffer::new::<SquareVertex>(
-        device.clone(),
-        memory_pool.clone(),
-        VERTICES.len(),
-    ) {
-        Ok(vertices) => vertices,
-        Err(err)     => { return Err(Error::BufferCreateError{ name: NAME, what: "vertex", err }); }
-    };
-
-    // Create the staging buffer
-    let bvertices: Rc<dyn Buffer> = vertices.clone();
-    let staging: Rc<StagingBuffer> = match StagingBuffer::new_for(&bvertices) {
-        Ok(staging) => staging,
-        Err(err)    => { return Err(Error::BufferCreateError{ name: NAME, what: "vertex staging", err }); }
-    };
-
-    // Populate the staging buffer
-    {
-        let mapped: MappedMemory = match staging.map() {
-            Ok(mapped) => mapped,
-            Err(err)   => { return Err(Error::BufferMapError{ name: NAME, what: "vertex staging", err }); }
-        };
-        mapped.as_slice_mut::<SquareVertex>(VERTICES.len()).clone_from_slice(&VERTICES);
-        if let Err(err) = mapped.flush() { return Err(Error::BufferFlushError{ name: NAME, what: "vertex staging", err }); }
-    }
-
-    // Copy the staging to the normal buffer
-    let tvertices: Rc<dyn TransferBuffer> = vertices.clone();
-    if let Err(err) = staging.copyto(command_pool, &tvertices) { return Err(Error::BufferCopyError{ name: NAME, src: "vertex staging", dst: "vertex", err }); }
-
-    // Done
-    Ok(vertices)
-}
-
-/// Creates, allocates and populates the index buffer.
-/// 
-/// # Arguments
-/// - `device`: The Device where the new Buffer will be allocated. Note that the Buffer's memory will be allocated on the device of the given `memory_pool`.
-/// - `memory_pool`: The MemoryPool where to allocate the memory for the index buffer (and a temporary staging buffer).
-/// - `command_pool`: The CommandPool where we will get a command buffer to do the copy on.
-fn create_index_buffer(device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>) -> Result<Rc<IndexBuffer>, Error> {
-    // Create the Index buffer object
-    let indices: Rc<IndexBuffer> = match IndexBuffer::new_u32(
-        device.clone(),
-        memory_pool.clone(),
-        INDICES.len(),
-    ) {
-        Ok(vertices) => vertices,
-        Err(err)     => { return Err(Error::BufferCreateError{ name: NAME, what: "index", err }); }
-    };
-
-    // Create the staging buffer
-    let bindices: Rc<dyn Buffer> = indices.clone();
-    let staging: Rc<StagingBuffer> = match StagingBuffer::new_for(&bindices) {
-        Ok(staging) => staging,
-        Err(err)    => { return Err(Error::BufferCreateError{ name: NAME, what: "index staging", err }); }
-    };
-
-    // Populate the staging buffer
-    {
-        let mapped: MappedMemory = match staging.map() {
-            Ok(mapped) => mapped,
-            Err(err)   => { return Err(Error::BufferMapError{ name: NAME, what: "index staging", err }); }
-        };
-        mapped.as_slice_mut::<u32>(INDICES.len()).clone_from_slice(&INDICES);
-        if let Err(err) = mapped.flush() { return Err(Error::BufferFlushError{ name: NAME, what: "index staging", err }); }
-    }
-
-    // Copy the staging to the normal buffer
-    let tindices: Rc<dyn TransferBuffer> = indices.clone();
-    if let Err(err) = staging.copyto(command_pool, &tindices) { return Err(Error::BufferCopyError{ name: NAME, src: "index staging", dst: "index", err }); }
-
-    // Done
-    Ok(indices)
-}
-
 /// Creates a new RenderPass for the Pipeline.
 /// 
 /// # Arguments
@@ -276,10 +192,9 @@ fn create_framebuffers(device: &Rc<Device>, render_pass: &Rc<RenderPass>, views:
 /// - `render_pass`: The RenderPass that we want to run in this buffer.
 /// - `pipeline`: The Pipeline that we want to run in this buffer.
 /// - `framebuffers`: The Framebuffers for which to record CommandBuffers.
-/// - `vertex_buffer`: The VertexBuffer to use for rendering.
-/// - `index_buffer`: The IndexBuffer to use for rendering.
+/// - `geometry`: The IndexedGeometry to draw.
 /// - `extent`: The portion of the Framebuffer to render to.
-fn record_command_buffers(device: &Rc<Device>, pool: &Rc<RefCell<CommandPool>>, render_pass: &Rc<RenderPass>, pipeline: &Rc<VkPipeline>, framebuffers: &[Rc<Framebuffer>], vertex_buffer: &Rc<VertexBuffer>, index_buffer: &Rc<IndexBuffer>, extent: &Extent2D<u32>) -> Result<Vec<Rc<CommandBuffer>>, Error> {
+fn record_command_buffers(device: &Rc<Device>, pool: &Rc<RefCell<CommandPool>>, render_pass: &Rc<RenderPass>, pipeline: &Rc<VkPipeline>, framebuffers: &[Rc<Framebuffer>], geometry: &IndexedGeometry, extent: &Extent2D<u32>) -> Result<Vec<Rc<CommandBuffer>>, Error> {
     // Record one command buffer per framebuffer
     let mut command_buffers: Vec<Rc<CommandBuffer>> = Vec::with_capacity(framebuffers.len());
     for framebuffer in framebuffers {
@@ -297,9 +212,7 @@ fn record_command_buffers(device: &Rc<Device>, pool: &Rc<RefCell<CommandPool>>,
         // Record the render pass with a single draw
         cmd.begin_render_pass(&render_pass, framebuffer, Rect2D::from_raw(Offset2D::new(0, 0), extent.clone()), &[[0.0, 0.0, 0.0, 1.0]]);
         cmd.bind_pipeline(BindPoint::Graphics, &pipeline);
-        cmd.bind_vertex_buffer(0, vertex_buffer);
-        cmd.bind_index_buffer(index_buffer);
-        cmd.draw_indexed(INDICES.len() as u32, 1, 0, 0, 0);
+        geometry.record_draw(&cmd);
         cmd.end_render_pass();
 
         // Finish recording
@@ -331,10 +244,8 @@ pub struct SquarePipeline {
     /// The target to which we render.
     target       : Rc<RefCell<dyn RenderTarget>>,
 
-    /// The vertex buffer for this pipeline.
-    vertex_buffer   : Rc<VertexBuffer>,
-    /// The index buffer for this pipeline.
-    index_buffer    : Rc<IndexBuffer>,
+    /// The vertex and index buffers for this pipeline.
+    geometry        : IndexedGeometry,
     /// The PipelineLayout that defines the resource layout of the pipeline.
     layout          : Rc<PipelineLayout>,
     /// The VkPipeline we wrap.
@@ -380,8 +291,7 @@ impl SquarePipeline {
         };
 
         // Build everything that depends on the Window
-        let vertex_buffer: Rc<VertexBuffer>;
-        let index_buffer: Rc<IndexBuffer>;
+        let geometry: IndexedGeometry;
         let pipeline: Rc<VkPipeline>;
         let framebuffers: Vec<Rc<Framebuffer>>;
         let command_buffers: Vec<Rc<CommandBuffer>>;
@@ -395,10 +305,7 @@ impl SquarePipeline {
 
             // Prepare the buffers
             debug!("[{}] Creating Buffers...", NAME);
-            debug!("[{}] Allocating Vertex buffer...", NAME);
-            vertex_buffer = create_vertex_buffer(&device, &memory_pool, &command_pool)?;
-            debug!("[{}] Allocating Index buffer...", NAME);
-            index_buffer  = create_index_buffer(&device, &memory_pool, &command_pool)?;
+            geometry = IndexedGeometry::new(NAME, &device, &memory_pool, &command_pool, &VERTICES, &INDICES)?;
 
             // Build the pipeline
             let extent = target.extent();
@@ -411,7 +318,7 @@ impl SquarePipeline {
 
             // Record one command buffer per framebuffer
             debug!("[{}] Recording CommandBuffers...", NAME);
-            command_buffers = record_command_buffers(&device, &command_pool, &render_pass, &pipeline, &framebuffers, &vertex_buffer, &index_buffer, &extent)?;
+            command_buffers = record_command_buffers(&device, &command_pool, &render_pass, &pipeline, &framebuffers, &geometry, &extent)?;
         }
 
         // Create the synchronization structures
@@ -445,8 +352,7 @@ impl SquarePipeline {
             command_pool,
             target,
 
-            vertex_buffer,
-            index_buffer,
+            geometry,
             layout,
             pipeline,
             framebuffers,
@@ -493,7 +399,7 @@ impl SquarePipeline {
             framebuffers = create_framebuffers(&self.device, &render_pass, &target.views(), &extent)?;
 
             // Record one command buffer per framebuffer
-            command_buffers = record_command_buffers(&self.device, &self.command_pool, &render_pass, &pipeline, &framebuffers, &self.vertex_buffer, &self.index_buffer, &extent)?;
+            command_buffers = record_command_buffers(&self.device, &self.command_pool, &render_pass, &pipeline, &framebuffers, &self.geometry, &extent)?;
         }
 
         // Overwrite some internal shit
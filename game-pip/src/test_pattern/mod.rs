@@ -0,0 +1,37 @@
+//  MOD.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:35:12
+//  Last edited:
+//    17 Oct 2026, 05:05:18
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   This module implements the TestPatternPipeline, which renders a
+//!   fullscreen test card (colour bars, gradients and an alignment grid)
+//!   to validate monitor setup, scaling and colour-space correctness.
+// 
+
+// Declare submodules
+pub mod vertex;
+pub mod pipeline;
+
+
+// Define constants
+/// The name of this specific pipeline
+pub const NAME: &'static str = "TestPattern";
+/// The folder with the compiled shaders of this pipeline. They are embedded in release builds, but read from here at runtime in debug builds.
+pub const SHADER_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/test_pattern/shaders/spir-v");
+
+
+// Load the shader files
+#[derive(rust_embed::RustEmbed)]
+#[folder = "$CARGO_MANIFEST_DIR/src/test_pattern/shaders/spir-v"]
+struct Shaders;
+
+
+// Bring some stuff into the module scope
+pub use vertex::TestPatternVertex as Vertex;
+pub use pipeline::TestPatternPipeline as Pipeline;
@@ -0,0 +1,120 @@
+/* SHADER.frag
+ *   by Lut99
+ *
+ * Created:
+ *   17 Oct 2026, 04:36:27
+ * Last edited:
+ *   17 Oct 2026, 05:55:10
+ * Auto updated?
+ *   Yes
+ *
+ * Description:
+ *   Fragment shader for the test pattern pipeline. Divides the screen in
+ *   colour bars (top), a greyscale gradient (middle) and red, green and
+ *   blue gradients (bottom), and overlays a pixel-exact alignment grid
+ *   with a border and a centre crosshair.
+ *
+ *   The pattern's values are meant as the values stored in the target.
+ *   When compiled with SRGB_TARGET (for sRGB targets, which encode our
+ *   output themselves), they are decoded first so that the encoding
+ *   gives back the same values as on a UNORM target.
+**/
+
+#version 450
+
+
+/***** LAYOUT *****/
+// The texture coordinate from the vertex shader passed to us
+layout(location = 0) in vec2 frag_uv;
+// The output colour of the fragment shader
+layout(location = 0) out vec4 out_colour;
+
+
+
+
+
+/***** CONSTANTS *****/
+// The colour bars, from left to right
+const vec3 BARS[8] = vec3[8](
+    vec3(1.0, 1.0, 1.0),
+    vec3(1.0, 1.0, 0.0),
+    vec3(0.0, 1.0, 1.0),
+    vec3(0.0, 1.0, 0.0),
+    vec3(1.0, 0.0, 1.0),
+    vec3(1.0, 0.0, 0.0),
+    vec3(0.0, 0.0, 1.0),
+    vec3(0.0, 0.0, 0.0)
+);
+
+// The number of grid cells in each direction
+const float GRID_CELLS = 16.0;
+// The colour of the grid lines
+const vec3 GRID_COLOUR = vec3(0.5, 0.5, 0.5);
+// The colour of the border and the centre crosshair
+const vec3 MARKER_COLOUR = vec3(1.0, 0.0, 1.0);
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+// Returns 1.0 if the given coordinate lies within one pixel of the given line position, or 0.0 otherwise.
+float on_line(float coord, float line, float pixel) {
+    return abs(coord - line) < pixel ? 1.0 : 0.0;
+}
+
+// Converts a value we want to end up in the target to what we should output for it.
+vec3 encode(vec3 colour) {
+#ifdef SRGB_TARGET
+    // Undo the sRGB encoding the hardware is about to apply
+    vec3 low  = colour / 12.92;
+    vec3 high = pow((colour + 0.055) / 1.055, vec3(2.4));
+    return mix(high, low, lessThanEqual(colour, vec3(0.04045)));
+#else
+    return colour;
+#endif
+}
+
+
+
+
+
+/***** ENTRYPOINT *****/
+void main() {
+    // Compute the base pattern for this third of the screen
+    vec3 colour;
+    if (frag_uv.y < 1.0 / 3.0) {
+        // Colour bars
+        colour = BARS[min(int(frag_uv.x * 8.0), 7)];
+    } else if (frag_uv.y < 2.0 / 3.0) {
+        // Greyscale gradient
+        colour = vec3(frag_uv.x);
+    } else {
+        // Red, green and blue gradients stacked vertically
+        int channel = min(int((frag_uv.y - 2.0 / 3.0) * 9.0), 2);
+        colour = vec3(0.0);
+        colour[channel] = frag_uv.x;
+    }
+
+    // Compute the size of a single pixel in UV-space, so the lines stay one pixel wide at any resolution
+    vec2 pixel = fwidth(frag_uv);
+
+    // Overlay the grid lines
+    vec2 cell = fract(frag_uv * GRID_CELLS);
+    vec2 cell_pixel = pixel * GRID_CELLS;
+    if (cell.x < cell_pixel.x || cell.y < cell_pixel.y) {
+        colour = GRID_COLOUR;
+    }
+
+    // Overlay the border and the centre crosshair
+    float marker = max(
+        max(on_line(frag_uv.x, 0.0, pixel.x), on_line(frag_uv.x, 1.0, pixel.x)),
+        max(on_line(frag_uv.y, 0.0, pixel.y), on_line(frag_uv.y, 1.0, pixel.y))
+    );
+    marker = max(marker, on_line(frag_uv.x, 0.5, pixel.x) * on_line(frag_uv.y, 0.5, 0.05));
+    marker = max(marker, on_line(frag_uv.y, 0.5, pixel.y) * on_line(frag_uv.x, 0.5, 0.05));
+    colour = mix(colour, MARKER_COLOUR, marker);
+
+    // Done
+    out_colour = vec4(encode(colour), 1.0);
+}
@@ -0,0 +1,38 @@
+/* SHADER.vert
+ *   by Lut99
+ *
+ * Created:
+ *   17 Oct 2026, 04:36:02
+ * Last edited:
+ *   17 Oct 2026, 04:36:02
+ * Auto updated?
+ *   Yes
+ *
+ * Description:
+ *   The vertex shader for the test pattern pipeline, which passes a
+ *   fullscreen quad and its texture coordinates.
+**/
+
+#version 450
+
+
+/***** LAYOUT *****/
+// The input vertex position
+layout(location = 0) in vec2 pos;
+// The input texture coordinate
+layout(location = 1) in vec2 uv;
+
+// The texture coordinate to pass to the fragment shader
+layout(location = 0) out vec2 frag_uv;
+
+
+
+
+
+/***** ENTRYPOINT *****/
+void main() {
+    // Simply pass the given position, except scaled to 4D
+    gl_Position = vec4(pos, 0.0, 1.0);
+    // Pass the texture coordinate
+    frag_uv = uv;
+}
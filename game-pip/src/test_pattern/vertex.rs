@@ -0,0 +1,59 @@
+//  VERTEX.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:35:40
+//  Last edited:
+//    17 Oct 2026, 04:35:40
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the Vertex interface for the TestPatternPipeline.
+// 
+
+use memoffset::offset_of;
+
+use rust_vk::auxillary::enums::AttributeLayout;
+use rust_vk::auxillary::structs::VertexAttribute;
+use rust_vk::pools::memory::spec::Vertex;
+
+
+/***** LIBRARY *****/
+/// The Vertex for the TestPatternPipeline
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct TestPatternVertex {
+    /// The coordinate of the vertex (in 2D screen space)
+    pub pos : [f32; 2],
+    /// The texture coordinate of the vertex (in the [0, 1] range), which the fragment shader uses to compute the pattern
+    pub uv  : [f32; 2],
+}
+
+impl Vertex for TestPatternVertex {
+    /// Returns the descriptions that list the attributes (=fields) for this Vertex.
+    /// 
+    /// # Returns
+    /// A list of VertexAttributeDescription that describes the attributes for this Vertex.
+    #[inline]
+    fn vk_attributes() -> Vec<VertexAttribute> {
+        vec![
+            VertexAttribute {
+                binding  : 0,
+                location : 0,
+                layout   : AttributeLayout::Float2,
+                offset   : offset_of!(TestPatternVertex, pos),
+            },
+            VertexAttribute {
+                binding  : 0,
+                location : 1,
+                layout   : AttributeLayout::Float2,
+                offset   : offset_of!(TestPatternVertex, uv),
+            }
+        ]
+    }
+
+    /// Returns the size (in bytes) of each Vertex.
+    #[inline]
+    fn vk_size() -> usize { std::mem::size_of::<Self>() }
+}
@@ -4,7 +4,7 @@
 //  Created:
 //    30 Apr 2022, 17:34:49
 //  Last edited:
-//    13 Aug 2022, 12:59:52
+//    17 Oct 2026, 05:05:18
 //  Auto updated?
 //    Yes
 // 
@@ -20,6 +20,8 @@ pub mod pipeline;
 // Define constants
 /// The name of this specific pipeline
 pub const NAME: &'static str = "Triangle";
+/// The folder with the compiled shaders of this pipeline. They are embedded in release builds, but read from here at runtime in debug builds.
+pub const SHADER_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/triangle/shaders/spir-v");
 
 
 // Load the shader files
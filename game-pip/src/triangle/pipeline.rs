@@ -4,7 +4,7 @@
 //  Created:
 //    30 Apr 2022, 16:56:20
 //  Last edited:
-//    20 Aug 2022, 14:43:20
+//    17 Oct 2026, 05:02:54
 //  Auto updated?
 //    Yes
 // 
@@ -26,7 +26,7 @@ use rust_vk::layout::PipelineLayout;
 use rust_vk::render_pass::{RenderPass, RenderPassBuilder};
 use rust_vk::pipeline::{Pipeline as VkPipeline, PipelineBuilder as VkPipelineBuilder};
 use rust_vk::pools::memory::prelude::*;
-use rust_vk::pools::memory::{MappedMemory, StagingBuffer, VertexBuffer};
+use rust_vk::pools::memory::VertexBuffer;
 use rust_vk::pools::command::{Buffer as CommandBuffer, Pool as CommandPool};
 use rust_vk::image;
 use rust_vk::framebuffer::Framebuffer;
@@ -38,6 +38,7 @@ use super::{NAME, Shaders};
 use super::vertex::TriangleVertex;
 
 pub use crate::errors::RenderPipelineError as Error;
+use crate::buffers::create_vertex_buffer;
 use crate::spec::RenderPipeline;
 
 
@@ -63,48 +64,6 @@ const VERTICES: [TriangleVertex; 3] = [
 
 
 /***** HELPER FUNCTIONS *****/
-/// Creates, allocates and populates the vertex buffer.
-/// 
-/// # Arguments
-/// - `device`: The Device where the new Buffer will be allocated. Note that the Buffer's memory will be allocated on the device of the given `memory_pool`.
-/// - `memory_pool`: The MemoryPool where to allocate the memory for the vertex buffer (and a temporary staging buffer).
-/// - `command_pool`: The CommandPool where we will get a command buffer to do the copy on.
-fn create_vertex_buffer(device: &Rc<Device>, memory_pool: &Rc<RefCell<dyn MemoryPool>>, command_pool: &Rc<RefCell<CommandPool>>) -> Result<Rc<VertexBuffer>, Error> {
-    // Create the Vertex buffer object
-    let vertices: Rc<VertexBuffer> = match VertexBuffer::new::<TriangleVertex>(
-        device.clone(),
-        memory_pool.clone(),
-        VERTICES.len(),
-    ) {
-        Ok(vertices) => vertices,
-        Err(err)     => { return Err(Error::BufferCreateError{ name: NAME, what: "vertex", err }); }
-    };
-
-    // Create the staging buffer
-    let bvertices: Rc<dyn Buffer> = vertices.clone();
-    let staging: Rc<StagingBuffer> = match StagingBuffer::new_for(&bvertices){
-        Ok(staging) => staging,
-        Err(err)    => { return Err(Error::BufferCreateError{ name: NAME, what: "vertex staging", err }); }
-    };
-
-    // Populate the staging buffer
-    {
-        let mapped: MappedMemory = match staging.map() {
-            Ok(mapped) => mapped,
-            Err(err)   => { return Err(Error::BufferMapError{ name: NAME, what: "vertex staging", err }); }
-        };
-        mapped.as_slice_mut::<TriangleVertex>(3).clone_from_slice(&VERTICES);
-        if let Err(err) = mapped.flush() { return Err(Error::BufferFlushError{ name: NAME, what: "vertex staging", err }); }
-    }
-
-    // Copy the staging to the normal buffer
-    let tvertices: Rc<dyn TransferBuffer> = vertices.clone();
-    if let Err(err) = staging.copyto(command_pool, &tvertices) { return Err(Error::BufferCopyError{ name: NAME, src: "vertex staging", dst: "vertex", err }); }
-
-    // Done
-    Ok(vertices)
-}
-
 /// Creates a new RenderPass for the Pipeline.
 /// 
 /// # Arguments
@@ -339,7 +298,7 @@ impl TrianglePipeline {
             let render_pass: Rc<RenderPass> = create_render_pass(&device, target.format())?;
 
             // Prepare the triangle buffer
-            vertex_buffer = create_vertex_buffer(&device, &memory_pool, &command_pool)?;
+            vertex_buffer = create_vertex_buffer(NAME, &device, &memory_pool, &command_pool, &VERTICES)?;
 
             // Build the pipeline
             let extent = target.extent();
@@ -0,0 +1,91 @@
+//  ERRORS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:28
+//  Last edited:
+//    17 Oct 2026, 05:41:51
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the errors for the save game library.
+// 
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::path::PathBuf;
+
+
+/***** LIBRARY *****/
+/// Errors that relate to writing and loading save games.
+#[derive(Debug)]
+pub enum SaveError {
+    /// The given slot name is not allowed.
+    IllegalSlotName{ slot: String },
+    /// The given slot does not exist.
+    UnknownSlot{ slot: String },
+    /// Could not prepare the save directory.
+    DirError{ err: game_cfg::Error },
+    /// Could not read the save directory.
+    DirReadError{ path: PathBuf, err: std::io::Error },
+
+    /// Could not serialize a section of the save game.
+    SerializeError{ key: &'static str, err: serde_json::Error },
+    /// Could not deserialize a section of the save game.
+    DeserializeError{ key: &'static str, err: serde_json::Error },
+
+    /// Could not open a save file.
+    OpenError{ path: PathBuf, err: std::io::Error },
+    /// Could not parse a save file.
+    ParseError{ path: PathBuf, err: serde_json::Error },
+    /// Could not create the temporary save file.
+    CreateError{ path: PathBuf, err: std::io::Error },
+    /// Could not write the temporary save file.
+    WriteError{ path: PathBuf, err: serde_json::Error },
+    /// Could not flush the temporary save file to disk.
+    SyncError{ path: PathBuf, err: std::io::Error },
+    /// Could not move the temporary save file over the old one.
+    RenameError{ from: PathBuf, to: PathBuf, err: std::io::Error },
+    /// Could not flush the save directory (and with it, the moved save file) to disk.
+    DirSyncError{ path: PathBuf, err: std::io::Error },
+    /// Could not delete a save file.
+    DeleteError{ path: PathBuf, err: std::io::Error },
+
+    /// The save file was written by a newer version of the game.
+    FutureVersion{ version: u32, current: u32 },
+    /// There is no migration from the given version to the next.
+    MissingMigration{ from: u32 },
+    /// A migration failed.
+    MigrationError{ from: u32, err: Box<dyn Error> },
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        use SaveError::*;
+        match self {
+            IllegalSlotName{ slot }   => write!(f, "Illegal save slot name '{}': only letters, digits, '-' and '_' are allowed", slot),
+            UnknownSlot{ slot }       => write!(f, "Save slot '{}' does not exist", slot),
+            DirError{ err }           => write!(f, "Could not prepare save directory: {}", err),
+            DirReadError{ path, err } => write!(f, "Could not read save directory '{}': {}", path.display(), err),
+
+            SerializeError{ key, err }   => write!(f, "Could not serialize save game section '{}': {}", key, err),
+            DeserializeError{ key, err } => write!(f, "Could not deserialize save game section '{}': {}", key, err),
+
+            OpenError{ path, err }        => write!(f, "Could not open save file '{}': {}", path.display(), err),
+            ParseError{ path, err }       => write!(f, "Could not parse save file '{}': {}", path.display(), err),
+            CreateError{ path, err }      => write!(f, "Could not create temporary save file '{}': {}", path.display(), err),
+            WriteError{ path, err }       => write!(f, "Could not write temporary save file '{}': {}", path.display(), err),
+            SyncError{ path, err }        => write!(f, "Could not flush temporary save file '{}' to disk: {}", path.display(), err),
+            RenameError{ from, to, err }  => write!(f, "Could not move temporary save file '{}' to '{}': {}", from.display(), to.display(), err),
+            DirSyncError{ path, err }     => write!(f, "Could not flush save directory '{}' to disk: {}", path.display(), err),
+            DeleteError{ path, err }      => write!(f, "Could not delete save file '{}': {}", path.display(), err),
+
+            FutureVersion{ version, current } => write!(f, "Save file has version {}, but this game only supports up to version {}", version, current),
+            MissingMigration{ from }          => write!(f, "No migration registered to upgrade save files from version {} to {}", from, from + 1),
+            MigrationError{ from, err }       => write!(f, "Failed to migrate save file from version {} to {}: {}", from, from + 1, err),
+        }
+    }
+}
+
+impl Error for SaveError {}
@@ -0,0 +1,28 @@
+//  LIB.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:28
+//  Last edited:
+//    17 Oct 2026, 04:41:28
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Entrypoint to the save game library, which writes selected
+//!   gameplay state to versioned save files and loads them back.
+// 
+
+// Define the submodules of this crate
+pub mod errors;
+pub mod spec;
+pub mod migrate;
+pub mod save;
+pub mod slots;
+
+// Pull some things into the crate namespace
+pub use errors::SaveError as Error;
+pub use spec::{Saveable, SaveHeader, SlotInfo};
+pub use migrate::Migrations;
+pub use save::SaveGame;
+pub use slots::SaveSlots;
@@ -0,0 +1,169 @@
+//  MIGRATE.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:28
+//  Last edited:
+//    17 Oct 2026, 05:41:51
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the migrations that upgrade older save files to the
+//!   current version.
+// 
+
+use std::collections::HashMap;
+use std::error::Error as StdError;
+
+use log::debug;
+use serde_json::{Map, Value};
+
+pub use crate::errors::SaveError as Error;
+
+
+/***** AUXILLARY TYPES *****/
+/// A single migration step, which upgrades the sections of a save file by one version.
+pub type Migration = Box<dyn Fn(Map<String, Value>) -> Result<Map<String, Value>, Box<dyn StdError>>>;
+
+
+
+
+
+/***** LIBRARY *****/
+/// Collects the migrations between save file versions.
+pub struct Migrations {
+    /// The version that save files are written with.
+    current : u32,
+    /// The migration steps, mapped by the version they upgrade from.
+    steps   : HashMap<u32, Migration>,
+}
+
+impl Migrations {
+    /// Constructor for the Migrations.
+    /// 
+    /// # Arguments
+    /// - `current`: The version of the save format that this game writes.
+    /// 
+    /// # Returns
+    /// A new Migrations without any steps.
+    #[inline]
+    pub fn new(current: u32) -> Self {
+        Self {
+            current,
+            steps : HashMap::new(),
+        }
+    }
+
+
+
+    /// Registers the migration that upgrades save files from the given version to the next.
+    /// 
+    /// # Arguments
+    /// - `from`: The version that this step upgrades from.
+    /// - `step`: The closure that takes the sections of the save file and returns them in the next version's format.
+    /// 
+    /// # Returns
+    /// A mutable reference to self for chaining purposes.
+    /// 
+    /// # Panics
+    /// This function panics if `from` is not older than the current version, or if there already is a step for it.
+    pub fn register<F>(&mut self, from: u32, step: F) -> &mut Self
+    where
+        F: 'static + Fn(Map<String, Value>) -> Result<Map<String, Value>, Box<dyn StdError>>,
+    {
+        if from >= self.current { panic!("Cannot register a migration from version {} when the current version is {}", from, self.current); }
+        if self.steps.insert(from, Box::new(step)).is_some() { panic!("Registered two migrations from version {}", from); }
+        self
+    }
+
+
+
+    /// Upgrades the sections of a save file to the current version.
+    /// 
+    /// # Arguments
+    /// - `version`: The version the sections are in.
+    /// - `sections`: The sections to upgrade.
+    /// 
+    /// # Returns
+    /// The sections in the current version's format.
+    /// 
+    /// # Errors
+    /// This function errors if the save file is newer than the current version, if a step is missing or if a step fails.
+    pub fn migrate(&self, version: u32, mut sections: Map<String, Value>) -> Result<Map<String, Value>, Error> {
+        if version > self.current { return Err(Error::FutureVersion{ version, current: self.current }); }
+
+        // Apply the steps one-by-one
+        for from in version..self.current {
+            let step: &Migration = match self.steps.get(&from) {
+                Some(step) => step,
+                None       => { return Err(Error::MissingMigration{ from }); }
+            };
+            debug!("Migrating save file from version {} to {}...", from, from + 1);
+            sections = match step(sections) {
+                Ok(sections) => sections,
+                Err(err)     => { return Err(Error::MigrationError{ from, err }); }
+            };
+        }
+
+        // Done
+        Ok(sections)
+    }
+
+
+
+    /// Returns the version of the save format that this game writes.
+    #[inline]
+    pub fn current(&self) -> u32 { self.current }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+
+    /// Returns migrations to version 2 that rename the `hp` section to `health` and then wrap it in an object.
+    fn migrations() -> Migrations {
+        let mut migrations = Migrations::new(2);
+        migrations.register(0, |mut sections| {
+            let hp: Value = sections.remove("hp").ok_or("no hp section")?;
+            sections.insert("health".into(), hp);
+            Ok(sections)
+        });
+        migrations.register(1, |mut sections| {
+            let health: Value = sections.remove("health").unwrap_or(Value::Null);
+            sections.insert("health".into(), json!({ "current": health }));
+            Ok(sections)
+        });
+        migrations
+    }
+
+
+    #[test]
+    fn test_migrate_applies_steps_in_order() {
+        let mut sections: Map<String, Value> = Map::new();
+        sections.insert("hp".into(), json!(7));
+
+        let sections: Map<String, Value> = migrations().migrate(0, sections).unwrap();
+        assert_eq!(sections.get("health"), Some(&json!({ "current": 7 })));
+        assert!(!sections.contains_key("hp"));
+
+        // Current files are left alone
+        let current: Map<String, Value> = migrations().migrate(2, sections.clone()).unwrap();
+        assert_eq!(current, sections);
+    }
+
+    #[test]
+    fn test_migrate_errors() {
+        assert!(matches!(migrations().migrate(3, Map::new()), Err(Error::FutureVersion{ version: 3, current: 2 })));
+        assert!(matches!(migrations().migrate(0, Map::new()), Err(Error::MigrationError{ from: 0, .. })));
+        assert!(matches!(Migrations::new(1).migrate(0, Map::new()), Err(Error::MissingMigration{ from: 0 })));
+    }
+}
@@ -0,0 +1,307 @@
+//  SAVE.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:28
+//  Last edited:
+//    17 Oct 2026, 05:41:51
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the SaveGame, which collects the gameplay state to save,
+//!   and the (atomic) reading and writing of save files.
+// 
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub use crate::errors::SaveError as Error;
+use crate::spec::{Saveable, SaveHeader};
+
+
+/***** HELPER STRUCTS *****/
+/// The layout of a save file on disk.
+#[derive(Deserialize)]
+pub(crate) struct SaveFile {
+    /// The header of the file.
+    pub(crate) header   : SaveHeader,
+    /// The saved state, mapped by the key of each Saveable.
+    pub(crate) sections : Map<String, Value>,
+}
+
+/// The layout of a save file on disk, but borrowing its contents (for writing).
+#[derive(Serialize)]
+struct SaveFileRef<'a> {
+    /// The header of the file.
+    header   : &'a SaveHeader,
+    /// The saved state, mapped by the key of each Saveable.
+    sections : &'a Map<String, Value>,
+}
+
+/// The layout of a save file on disk, but only the header (so listing slots doesn't parse all the state).
+#[derive(Deserialize)]
+pub(crate) struct SaveFileHeader {
+    /// The header of the file.
+    pub(crate) header : SaveHeader,
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Reads a save file (or only its header) from disk.
+/// 
+/// # Arguments
+/// - `path`: The path of the save file.
+/// 
+/// # Errors
+/// This function errors if we could not open or parse the file.
+pub(crate) fn read<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, Error> {
+    let handle = match File::open(path) {
+        Ok(handle) => handle,
+        Err(err)   => { return Err(Error::OpenError{ path: path.to_path_buf(), err }); }
+    };
+    match serde_json::from_reader(BufReader::new(handle)) {
+        Ok(file) => Ok(file),
+        Err(err) => Err(Error::ParseError{ path: path.to_path_buf(), err }),
+    }
+}
+
+/// Writes a save file to the given (temporary) path and flushes it to disk.
+/// 
+/// # Arguments
+/// - `tmp_path`: The path of the temporary file.
+/// - `header`: The header of the save file.
+/// - `sections`: The saved state to write.
+/// 
+/// # Errors
+/// This function errors if we failed to create, write or flush the file.
+fn write_tmp(tmp_path: &Path, header: &SaveHeader, sections: &Map<String, Value>) -> Result<(), Error> {
+    let handle = match File::create(tmp_path) {
+        Ok(handle) => handle,
+        Err(err)   => { return Err(Error::CreateError{ path: tmp_path.to_path_buf(), err }); }
+    };
+    let mut writer = BufWriter::new(handle);
+    if let Err(err) = serde_json::to_writer(&mut writer, &SaveFileRef{ header, sections }) { return Err(Error::WriteError{ path: tmp_path.to_path_buf(), err }); }
+    let handle: File = match writer.into_inner() {
+        Ok(handle) => handle,
+        Err(err)   => { return Err(Error::SyncError{ path: tmp_path.to_path_buf(), err: err.into_error() }); }
+    };
+    match handle.sync_all() {
+        Ok(_)    => Ok(()),
+        Err(err) => Err(Error::SyncError{ path: tmp_path.to_path_buf(), err }),
+    }
+}
+
+/// Flushes the directory that contains the given file to disk, so a rename into it survives a crash.
+/// 
+/// Only does something on Unix, as directories cannot be opened (and thus synced) on other platforms.
+/// 
+/// # Arguments
+/// - `path`: The path of the file whose directory to flush.
+/// 
+/// # Errors
+/// This function errors if the directory could not be opened or flushed.
+fn sync_dir(path: &Path) -> Result<(), Error> {
+    #[cfg(unix)]
+    {
+        let dir: &Path = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _                                        => Path::new("."),
+        };
+        let handle = match File::open(dir) {
+            Ok(handle) => handle,
+            Err(err)   => { return Err(Error::DirSyncError{ path: dir.to_path_buf(), err }); }
+        };
+        if let Err(err) = handle.sync_all() { return Err(Error::DirSyncError{ path: dir.to_path_buf(), err }); }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Writes a save file to disk without ever leaving a half-written file at `path`.
+/// 
+/// The file is first written to a temporary file next to it and flushed to disk, after which it is moved over the old file and (on Unix) the directory is flushed too.
+/// 
+/// # Arguments
+/// - `path`: The path of the save file.
+/// - `header`: The header of the save file.
+/// - `sections`: The saved state to write.
+/// 
+/// # Errors
+/// This function errors if we failed to write the temporary file or to move it into place. In either case, any old file at `path` is left untouched and the temporary file is removed again. It also errors if the directory could not be flushed, in which case the new file is in place but may not survive a crash.
+pub(crate) fn write_atomic(path: &Path, header: &SaveHeader, sections: &Map<String, Value>) -> Result<(), Error> {
+    let mut tmp_path: PathBuf = path.to_path_buf();
+    tmp_path.set_extension("tmp");
+
+    // Write the temporary file and swap it with the old one, cleaning up after ourselves if either fails
+    let res: Result<(), Error> = write_tmp(&tmp_path, header, sections).and_then(|_| match fs::rename(&tmp_path, path) {
+        Ok(_)    => Ok(()),
+        Err(err) => Err(Error::RenameError{ from: tmp_path.clone(), to: path.to_path_buf(), err }),
+    });
+    if let Err(err) = res {
+        if tmp_path.exists() {
+            if let Err(err) = fs::remove_file(&tmp_path) { warn!("Could not remove temporary save file '{}': {}", tmp_path.display(), err); }
+        }
+        return Err(err);
+    }
+
+    // Make sure the rename itself is on disk
+    sync_dir(path)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Collects the gameplay state that makes up a single save game.
+/// 
+/// Every Saveable is stored in its own section, so systems can add their state independently and sections that a version of the game does not know about are simply ignored.
+#[derive(Clone, Debug)]
+pub struct SaveGame {
+    /// The user-facing name of the save.
+    pub name : String,
+    /// The saved state, mapped by the key of each Saveable.
+    sections : Map<String, Value>,
+}
+
+impl SaveGame {
+    /// Constructor for the SaveGame.
+    /// 
+    /// # Arguments
+    /// - `name`: The user-facing name of the save.
+    /// 
+    /// # Returns
+    /// A new SaveGame without any state in it.
+    #[inline]
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name     : name.into(),
+            sections : Map::new(),
+        }
+    }
+
+    /// Constructor for the SaveGame that re-creates it from (already migrated) sections.
+    #[inline]
+    pub(crate) fn from_sections(name: String, sections: Map<String, Value>) -> Self {
+        Self {
+            name,
+            sections,
+        }
+    }
+
+
+
+    /// Adds the given state to the save game, overwriting any previous state with the same key.
+    /// 
+    /// # Arguments
+    /// - `value`: The state to save.
+    /// 
+    /// # Errors
+    /// This function errors if we failed to serialize the state.
+    pub fn put<T: Saveable>(&mut self, value: &T) -> Result<(), Error> {
+        let value: Value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(err)  => { return Err(Error::SerializeError{ key: T::KEY, err }); }
+        };
+        self.sections.insert(T::KEY.into(), value);
+        Ok(())
+    }
+
+    /// Returns the given state from the save game.
+    /// 
+    /// # Returns
+    /// The state, or `None` if the save game does not have it (e.g., because it is older than the state).
+    /// 
+    /// # Errors
+    /// This function errors if the saved state could not be deserialized.
+    pub fn get<T: Saveable>(&self) -> Result<Option<T>, Error> {
+        let value: &Value = match self.sections.get(T::KEY) {
+            Some(value) => value,
+            None        => { return Ok(None); }
+        };
+        match T::deserialize(value) {
+            Ok(value) => Ok(Some(value)),
+            Err(err)  => Err(Error::DeserializeError{ key: T::KEY, err }),
+        }
+    }
+
+    /// Returns whether the save game has the given state.
+    #[inline]
+    pub fn contains<T: Saveable>(&self) -> bool { self.sections.contains_key(T::KEY) }
+
+
+
+    /// Returns the raw sections of the save game.
+    #[inline]
+    pub fn sections(&self) -> &Map<String, Value> { &self.sections }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use serde_json::json;
+
+    use super::*;
+
+
+    /// Returns a fresh, empty directory for the given test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir: PathBuf = env::temp_dir().join(format!("game-sav-{}-{}", process::id(), name));
+        if dir.exists() { fs::remove_dir_all(&dir).unwrap(); }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Returns a header for the test files.
+    fn header() -> SaveHeader { SaveHeader{ version: 3, name: "Test".into(), timestamp: 42 } }
+
+
+    #[test]
+    fn test_write_atomic_round_trip() {
+        let dir: PathBuf = test_dir("write-atomic");
+        let path: PathBuf = dir.join("slot.sav");
+        let mut sections: Map<String, Value> = Map::new();
+        sections.insert("player".into(), json!({ "x": 1.5, "hp": 10 }));
+
+        write_atomic(&path, &header(), &sections).unwrap();
+        let file: SaveFile = read(&path).unwrap();
+        assert_eq!(file.header.version, 3);
+        assert_eq!(file.header.name, "Test");
+        assert_eq!(file.header.timestamp, 42);
+        assert_eq!(file.sections, sections);
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_atomic_cleans_up_on_error() {
+        let dir: PathBuf = test_dir("write-atomic-error");
+        let path: PathBuf = dir.join("slot.sav");
+
+        // A directory in the way makes the rename fail
+        fs::create_dir(&path).unwrap();
+        assert!(matches!(write_atomic(&path, &header(), &Map::new()), Err(Error::RenameError{ .. })));
+        assert!(path.is_dir());
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,314 @@
+//  SLOTS.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:28
+//  Last edited:
+//    17 Oct 2026, 05:41:51
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements the SaveSlots, which manages the save files in the save
+//!   directory.
+// 
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use log::{debug, warn};
+
+pub use crate::errors::SaveError as Error;
+use crate::spec::{SaveHeader, SlotInfo};
+use crate::migrate::Migrations;
+use crate::save::{read, write_atomic, SaveFile, SaveFileHeader, SaveGame};
+
+
+/***** CONSTANTS *****/
+/// The extension of save files.
+const SAVE_EXTENSION: &str = "sav";
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Checks whether the given slot name is safe to use as a filename.
+#[inline]
+fn is_valid_slot(slot: &str) -> bool {
+    !slot.is_empty() && slot.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Manages the save slots in the save directory.
+pub struct SaveSlots {
+    /// The directory with the save files.
+    dir        : PathBuf,
+    /// The migrations that upgrade older save files.
+    migrations : Migrations,
+}
+
+impl SaveSlots {
+    /// Constructor for the SaveSlots.
+    /// 
+    /// # Arguments
+    /// - `dir`: The directory with the save files (typically `Config::dirs.saves`). It is created when the first game is saved.
+    /// - `migrations`: The migrations that upgrade older save files. Its current version is the version that new saves are written with.
+    /// 
+    /// # Returns
+    /// A new SaveSlots instance.
+    #[inline]
+    pub fn new<P: Into<PathBuf>>(dir: P, migrations: Migrations) -> Self {
+        Self {
+            dir : dir.into(),
+            migrations,
+        }
+    }
+
+
+
+    /// Returns the path of the save file for the given slot.
+    /// 
+    /// # Errors
+    /// This function errors if the slot name is not a valid name.
+    pub fn path(&self, slot: &str) -> Result<PathBuf, Error> {
+        if !is_valid_slot(slot) { return Err(Error::IllegalSlotName{ slot: slot.into() }); }
+        Ok(self.dir.join(format!("{}.{}", slot, SAVE_EXTENSION)))
+    }
+
+    /// Lists the save slots in the save directory, newest first.
+    /// 
+    /// Save files that cannot be read are skipped with a warning, so a single corrupt file does not hide the others.
+    /// 
+    /// # Errors
+    /// This function errors if we could not read the save directory. If it does not exist, no slots are returned instead.
+    pub fn list(&self) -> Result<Vec<SlotInfo>, Error> {
+        if !self.dir.exists() { return Ok(vec![]); }
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err)    => { return Err(Error::DirReadError{ path: self.dir.clone(), err }); }
+        };
+
+        // Collect the headers of all save files
+        let mut slots: Vec<SlotInfo> = vec![];
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err)  => { return Err(Error::DirReadError{ path: self.dir.clone(), err }); }
+            };
+            let path: PathBuf = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SAVE_EXTENSION) { continue; }
+            let slot: String = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(slot) if is_valid_slot(slot) => slot.into(),
+                _                                 => { continue; }
+            };
+
+            // Read the header only
+            match read::<SaveFileHeader>(&path) {
+                Ok(file) => { slots.push(SlotInfo{ slot, path, header: file.header }); },
+                Err(err) => { warn!("Skipping save slot '{}': {}", slot, err); },
+            }
+        }
+
+        // Sort them by time, newest first
+        slots.sort_by_key(|info| Reverse(info.header.timestamp));
+        Ok(slots)
+    }
+
+    /// Returns whether the given slot has a save file.
+    #[inline]
+    pub fn exists(&self, slot: &str) -> bool {
+        self.path(slot).map(|path| path.is_file()).unwrap_or(false)
+    }
+
+
+
+    /// Writes the given SaveGame to the given slot, replacing any previous save in it.
+    /// 
+    /// The save is written atomically: if anything goes wrong, the previous save in the slot is left untouched.
+    /// 
+    /// # Arguments
+    /// - `slot`: The name of the slot to save to.
+    /// - `game`: The SaveGame to write.
+    /// 
+    /// # Errors
+    /// This function errors if the slot name is invalid, if the save directory could not be created or if writing failed.
+    pub fn save(&self, slot: &str, game: &SaveGame) -> Result<(), Error> {
+        let path: PathBuf = self.path(slot)?;
+        if let Err(err) = game_cfg::paths::ensure_dir("save", &self.dir) { return Err(Error::DirError{ err }); }
+
+        // Write it with a fresh header
+        let header = SaveHeader {
+            version   : self.migrations.current(),
+            name      : game.name.clone(),
+            timestamp : Utc::now().timestamp(),
+        };
+        debug!("Saving game '{}' to '{}'...", game.name, path.display());
+        write_atomic(&path, &header, game.sections())
+    }
+
+    /// Loads the SaveGame in the given slot, migrating it to the current version if needed.
+    /// 
+    /// # Arguments
+    /// - `slot`: The name of the slot to load.
+    /// 
+    /// # Returns
+    /// The header as it was on disk and the (migrated) SaveGame.
+    /// 
+    /// # Errors
+    /// This function errors if the slot does not exist, if the file could not be read or if it could not be migrated.
+    pub fn load(&self, slot: &str) -> Result<(SaveHeader, SaveGame), Error> {
+        let path: PathBuf = self.path(slot)?;
+        if !path.is_file() { return Err(Error::UnknownSlot{ slot: slot.into() }); }
+
+        // Read and migrate it
+        debug!("Loading save game from '{}'...", path.display());
+        let file: SaveFile = read(&path)?;
+        let sections = self.migrations.migrate(file.header.version, file.sections)?;
+        let game = SaveGame::from_sections(file.header.name.clone(), sections);
+        Ok((file.header, game))
+    }
+
+    /// Deletes the save file in the given slot.
+    /// 
+    /// # Errors
+    /// This function errors if the slot does not exist or if the file could not be removed.
+    pub fn delete(&self, slot: &str) -> Result<(), Error> {
+        let path: PathBuf = self.path(slot)?;
+        if !path.is_file() { return Err(Error::UnknownSlot{ slot: slot.into() }); }
+        match fs::remove_file(&path) {
+            Ok(_)    => Ok(()),
+            Err(err) => Err(Error::DeleteError{ path, err }),
+        }
+    }
+
+
+
+    /// Returns the directory with the save files.
+    #[inline]
+    pub fn dir(&self) -> &Path { &self.dir }
+
+    /// Returns the migrations used when loading save files.
+    #[inline]
+    pub fn migrations(&self) -> &Migrations { &self.migrations }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Map, Value};
+
+    use super::*;
+    use crate::spec::Saveable;
+
+
+    /// Some state to save.
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Player {
+        /// The position of the player.
+        pos : (f32, f32),
+        /// The health of the player.
+        hp  : u32,
+    }
+    impl Saveable for Player {
+        const KEY: &'static str = "player";
+    }
+
+
+    /// Returns a fresh save directory for the given test (which does not exist yet).
+    fn test_dir(name: &str) -> PathBuf {
+        let dir: PathBuf = env::temp_dir().join(format!("game-sav-{}-{}", process::id(), name));
+        if dir.exists() { fs::remove_dir_all(&dir).unwrap(); }
+        dir
+    }
+
+
+    #[test]
+    fn test_save_load_round_trip() {
+        let dir: PathBuf = test_dir("round-trip");
+        let slots = SaveSlots::new(&dir, Migrations::new(1));
+
+        let mut game = SaveGame::new("My game");
+        game.put(&Player{ pos: (1.0, 2.0), hp: 10 }).unwrap();
+        slots.save("quick", &game).unwrap();
+        assert!(slots.exists("quick"));
+
+        let (header, loaded): (SaveHeader, SaveGame) = slots.load("quick").unwrap();
+        assert_eq!(header.version, 1);
+        assert_eq!(header.name, "My game");
+        assert_eq!(loaded.name, "My game");
+        assert_eq!(loaded.get::<Player>().unwrap(), Some(Player{ pos: (1.0, 2.0), hp: 10 }));
+
+        slots.delete("quick").unwrap();
+        assert!(!slots.exists("quick"));
+        assert!(matches!(slots.load("quick"), Err(Error::UnknownSlot{ .. })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_migrates_old_saves() {
+        let dir: PathBuf = test_dir("migrate");
+
+        // Write a save with version 0, in which the player had no health yet
+        let mut sections: Map<String, Value> = Map::new();
+        sections.insert(Player::KEY.into(), json!({ "pos": [ 3.0, 4.0 ] }));
+        let game = SaveGame::from_sections("Old game".into(), sections);
+        SaveSlots::new(&dir, Migrations::new(0)).save("old", &game).unwrap();
+
+        // Load it with a newer version of the game
+        let mut migrations = Migrations::new(1);
+        migrations.register(0, |mut sections| {
+            if let Some(Value::Object(player)) = sections.get_mut(Player::KEY) { player.insert("hp".into(), json!(100)); }
+            Ok(sections)
+        });
+        let (header, loaded): (SaveHeader, SaveGame) = SaveSlots::new(&dir, migrations).load("old").unwrap();
+        assert_eq!(header.version, 0);
+        assert_eq!(loaded.get::<Player>().unwrap(), Some(Player{ pos: (3.0, 4.0), hp: 100 }));
+
+        // Saves from the future are refused
+        let future = SaveSlots::new(&dir, Migrations::new(2));
+        future.save("future", &game).unwrap();
+        assert!(matches!(SaveSlots::new(&dir, Migrations::new(1)).load("future"), Err(Error::FutureVersion{ version: 2, current: 1 })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_list_slots() {
+        let dir: PathBuf = test_dir("list");
+        let slots = SaveSlots::new(&dir, Migrations::new(0));
+        assert!(slots.list().unwrap().is_empty());
+
+        // Write two saves with different timestamps, a corrupt one and an unrelated file
+        fs::create_dir_all(&dir).unwrap();
+        for (slot, timestamp) in [ ("first", 100), ("second", 200) ] {
+            let header = SaveHeader{ version: 0, name: slot.into(), timestamp };
+            write_atomic(&slots.path(slot).unwrap(), &header, &Map::new()).unwrap();
+        }
+        fs::write(dir.join("corrupt.sav"), "{ not json").unwrap();
+        fs::write(dir.join("notes.txt"), "not a save").unwrap();
+
+        let list: Vec<SlotInfo> = slots.list().unwrap();
+        let names: Vec<&str> = list.iter().map(|info| info.slot.as_str()).collect();
+        assert_eq!(names, vec![ "second", "first" ]);
+        assert_eq!(list[0].header.timestamp, 200);
+
+        // Slot names cannot escape the directory
+        assert!(matches!(slots.path("../evil"), Err(Error::IllegalSlotName{ .. })));
+        assert!(matches!(slots.path(""), Err(Error::IllegalSlotName{ .. })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+//  SPEC.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:28
+//  Last edited:
+//    17 Oct 2026, 04:41:28
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Defines the public interfaces of the save game library.
+// 
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+
+
+/***** LIBRARY *****/
+/// Marks a piece of gameplay state (a component, a resource, ...) as something that should end up in a save game.
+pub trait Saveable: Serialize + DeserializeOwned {
+    /// The unique key of this section in the save file. Should never change, or older save files won't find it anymore (use a migration instead).
+    const KEY: &'static str;
+}
+
+
+
+/// The header that precedes every save file.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SaveHeader {
+    /// The version of the save format that the file was written with.
+    pub version   : u32,
+    /// The user-facing name of the save.
+    pub name      : String,
+    /// The moment the save was written, as seconds since the Unix epoch.
+    pub timestamp : i64,
+}
+
+
+
+/// Describes a save slot found on disk, for use in a load menu.
+#[derive(Clone, Debug)]
+pub struct SlotInfo {
+    /// The name of the slot.
+    pub slot   : String,
+    /// The path of the slot's save file.
+    pub path   : PathBuf,
+    /// The header of the slot's save file.
+    pub header : SaveHeader,
+}
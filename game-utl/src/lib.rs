@@ -4,7 +4,7 @@
  * Created:
  *   27 Mar 2022, 11:44:36
  * Last edited:
- *   03 Apr 2022, 15:13:32
+ *   17 Oct 2026, 05:06:01
  * Auto updated?
  *   Yes
  *
@@ -16,6 +16,10 @@
 // pub mod errors;
 /// Module that contains the common traits.
 pub mod traits;
+/// Module that contains the typed handle registry.
+pub mod registry;
+/// Module that contains the undo/redo stack.
+pub mod undo;
 // /// Module that contains the common functions.
 // pub mod utils;
 
@@ -0,0 +1,380 @@
+//  REGISTRY.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 04:41:53
+//  Last edited:
+//    17 Oct 2026, 05:48:56
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements a generic Registry that stores resources behind typed,
+//!   generational handles and defers their destruction by a number of
+//!   frames.
+// 
+
+use std::fmt::{Debug, Formatter, Result as FResult};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+
+/***** AUXILLARY STRUCTS *****/
+/// A single slot in the Registry.
+struct Slot<T> {
+    /// The generation of this slot. Is bumped every time the slot's value is removed, so old handles become invalid.
+    generation : u32,
+    /// The value in this slot, if any.
+    value      : Option<T>,
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A typed handle that references a resource in a Registry.
+/// 
+/// Handles are cheap to copy, and become invalid (instead of referencing another resource) once the resource they point to is removed.
+pub struct Handle<T> {
+    /// The index of the slot in the Registry.
+    index      : u32,
+    /// The generation of the slot when this handle was created.
+    generation : u32,
+
+    /// Binds the handle to the type it references.
+    _type : PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+    /// Returns the index of the slot this handle points to.
+    #[inline]
+    pub fn index(&self) -> u32 { self.index }
+
+    /// Returns the generation of the slot this handle points to.
+    #[inline]
+    pub fn generation(&self) -> u32 { self.generation }
+}
+
+impl<T> Clone for Handle<T> {
+    #[inline]
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> Debug for Handle<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        write!(f, "Handle({}v{})", self.index, self.generation)
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { self.index == other.index && self.generation == other.generation }
+}
+
+impl<T> Hash for Handle<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+
+
+/// Stores resources of a single type behind typed Handles.
+/// 
+/// Removed or replaced resources are not dropped immediately, but retired until `delay` more frames have passed (see `Registry::next_frame()`). This way, resources that may still be in use by frames in flight are kept alive long enough.
+pub struct Registry<T> {
+    /// The slots that contain the resources.
+    slots : Vec<Slot<T>>,
+    /// The indices of the slots that are free to use again.
+    free  : Vec<u32>,
+
+    /// The resources that have been removed, together with the frame at which they were removed.
+    retired : Vec<(u64, T)>,
+    /// The current frame.
+    frame   : u64,
+    /// The number of frames that retired resources are kept alive.
+    delay   : u64,
+}
+
+impl<T> Registry<T> {
+    /// Constructor for the Registry.
+    /// 
+    /// # Arguments
+    /// - `delay`: The number of frames that removed resources are kept alive. Typically equal to the number of frames in flight.
+    /// 
+    /// # Returns
+    /// A new, empty Registry.
+    #[inline]
+    pub fn new(delay: usize) -> Self {
+        Self {
+            slots : vec![],
+            free  : vec![],
+
+            retired : vec![],
+            frame   : 0,
+            delay   : delay as u64,
+        }
+    }
+
+
+
+    /// Adds a new resource to the Registry.
+    /// 
+    /// # Arguments
+    /// - `value`: The resource to add.
+    /// 
+    /// # Returns
+    /// A new Handle that references the resource.
+    pub fn insert(&mut self, value: T) -> Handle<T> {
+        // Re-use a free slot if there is any
+        if let Some(index) = self.free.pop() {
+            let slot: &mut Slot<T> = &mut self.slots[index as usize];
+            slot.value = Some(value);
+            return Handle{ index, generation: slot.generation, _type: PhantomData };
+        }
+
+        // Otherwise, add a new one
+        let index: u32 = self.slots.len() as u32;
+        self.slots.push(Slot{ generation: 0, value: Some(value) });
+        Handle{ index, generation: 0, _type: PhantomData }
+    }
+
+    /// Replaces the resource behind the given Handle with a new one, retiring the old one.
+    /// 
+    /// The Handle stays valid, and references the new resource from now on.
+    /// 
+    /// # Arguments
+    /// - `handle`: The Handle of the resource to replace.
+    /// - `value`: The new resource.
+    /// 
+    /// # Returns
+    /// Whether the Handle was valid. If it wasn't, the given `value` is dropped.
+    pub fn replace(&mut self, handle: Handle<T>, value: T) -> bool {
+        // Get the slot and check the handle is still valid
+        let slot: &mut Slot<T> = match self.slots.get_mut(handle.index as usize) {
+            Some(slot) => slot,
+            None       => { return false; }
+        };
+        if slot.generation != handle.generation || slot.value.is_none() { return false; }
+
+        // Swap the values, then retire the old one
+        let old: T = slot.value.replace(value).unwrap();
+        self.retired.push((self.frame, old));
+        true
+    }
+
+    /// Removes the resource behind the given Handle, retiring it.
+    /// 
+    /// The Handle (and any copies of it) are invalid afterwards.
+    /// 
+    /// # Arguments
+    /// - `handle`: The Handle of the resource to remove.
+    /// 
+    /// # Returns
+    /// Whether the Handle was valid.
+    pub fn remove(&mut self, handle: Handle<T>) -> bool {
+        // Get the slot and check the handle is still valid
+        let slot: &mut Slot<T> = match self.slots.get_mut(handle.index as usize) {
+            Some(slot) => slot,
+            None       => { return false; }
+        };
+        if slot.generation != handle.generation { return false; }
+
+        // Take the value out of it and retire it
+        let old: T = match slot.value.take() {
+            Some(old) => old,
+            None      => { return false; }
+        };
+        self.retired.push((self.frame, old));
+
+        // Invalidate any outstanding handles and mark the slot as free
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.index);
+        true
+    }
+
+
+
+    /// Marks the start of a new frame, dropping any retired resources that are old enough.
+    pub fn next_frame(&mut self) {
+        self.frame += 1;
+
+        // Drop everything that has been retired for long enough
+        let (frame, delay): (u64, u64) = (self.frame, self.delay);
+        self.retired.retain(|(retired, _)| frame - retired <= delay);
+    }
+
+    /// Drops all retired resources immediately.
+    /// 
+    /// Only call this when it is known that none of them are in use anymore (e.g., after waiting for the Device to become idle).
+    #[inline]
+    pub fn flush(&mut self) { self.retired.clear(); }
+
+
+
+    /// Returns the resource behind the given Handle.
+    /// 
+    /// # Returns
+    /// A reference to the resource, or `None` if the Handle is no longer valid.
+    #[inline]
+    pub fn get(&self, handle: Handle<T>) -> Option<&T> {
+        match self.slots.get(handle.index as usize) {
+            Some(slot) if slot.generation == handle.generation => slot.value.as_ref(),
+            _                                                  => None,
+        }
+    }
+
+    /// Returns the resource behind the given Handle, mutably.
+    /// 
+    /// # Returns
+    /// A mutable reference to the resource, or `None` if the Handle is no longer valid.
+    #[inline]
+    pub fn get_mut(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        match self.slots.get_mut(handle.index as usize) {
+            Some(slot) if slot.generation == handle.generation => slot.value.as_mut(),
+            _                                                  => None,
+        }
+    }
+
+    /// Returns whether the given Handle still references a resource.
+    #[inline]
+    pub fn contains(&self, handle: Handle<T>) -> bool { self.get(handle).is_some() }
+
+    /// Returns an iterator over all live resources and their Handles.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.slots.iter().enumerate().filter_map(|(i, slot)| {
+            slot.value.as_ref().map(|value| (Handle{ index: i as u32, generation: slot.generation, _type: PhantomData }, value))
+        })
+    }
+
+
+
+    /// Returns the number of live resources in the Registry.
+    #[inline]
+    pub fn len(&self) -> usize { self.slots.len() - self.free.len() }
+
+    /// Returns whether the Registry has no live resources.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns the number of retired resources that are still kept alive.
+    #[inline]
+    pub fn n_retired(&self) -> usize { self.retired.len() }
+}
+
+impl<T> Default for Registry<T> {
+    #[inline]
+    fn default() -> Self { Self::new(0) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+
+    #[test]
+    fn test_stale_handle_after_reuse() {
+        let mut registry: Registry<&str> = Registry::new(0);
+        let a: Handle<&str> = registry.insert("a");
+        assert!(registry.remove(a));
+
+        // The new value re-uses the slot, but the old handle doesn't see it
+        let b: Handle<&str> = registry.insert("b");
+        assert_eq!(a.index(), b.index());
+        assert_ne!(a, b);
+        assert_eq!(registry.get(a), None);
+        assert_eq!(registry.get(b), Some(&"b"));
+        assert!(!registry.contains(a));
+
+        // Nor can it remove or replace it
+        assert!(!registry.remove(a));
+        assert!(!registry.replace(a, "c"));
+        assert_eq!(registry.get(b), Some(&"b"));
+    }
+
+    #[test]
+    fn test_replace_keeps_handle() {
+        let mut registry: Registry<Rc<()>> = Registry::new(1);
+        let old: Rc<()> = Rc::new(());
+        let handle: Handle<Rc<()>> = registry.insert(old.clone());
+
+        let new: Rc<()> = Rc::new(());
+        assert!(registry.replace(handle, new.clone()));
+        assert!(Rc::ptr_eq(registry.get(handle).unwrap(), &new));
+        assert_eq!(registry.len(), 1);
+
+        // The old value is retired, not dropped
+        assert_eq!(registry.n_retired(), 1);
+        assert_eq!(Rc::strong_count(&old), 2);
+    }
+
+    #[test]
+    fn test_retire_boundary() {
+        let mut registry: Registry<Rc<()>> = Registry::new(2);
+        let value: Rc<()> = Rc::new(());
+        let handle: Handle<Rc<()>> = registry.insert(value.clone());
+        assert!(registry.remove(handle));
+
+        // Kept alive for exactly `delay` more frames
+        registry.next_frame();
+        registry.next_frame();
+        assert_eq!(registry.n_retired(), 1);
+        assert_eq!(Rc::strong_count(&value), 2);
+        registry.next_frame();
+        assert_eq!(registry.n_retired(), 0);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        // Flushing drops them immediately
+        let handle: Handle<Rc<()>> = registry.insert(value.clone());
+        assert!(registry.remove(handle));
+        registry.flush();
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut registry: Registry<u32> = Registry::default();
+        assert!(registry.is_empty());
+        let handles: Vec<Handle<u32>> = (0..4).map(|i| registry.insert(i)).collect();
+        assert_eq!(registry.len(), 4);
+
+        assert!(registry.remove(handles[1]));
+        assert!(registry.remove(handles[2]));
+        assert!(!registry.remove(handles[2]));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.iter().map(|(_, v)| *v).collect::<Vec<u32>>(), vec![ 0, 3 ]);
+
+        registry.insert(5);
+        assert_eq!(registry.len(), 3);
+    }
+
+    #[test]
+    fn test_generation_wraps() {
+        let mut registry: Registry<u32> = Registry::new(0);
+        let first: Handle<u32> = registry.insert(1);
+        registry.slots[first.index() as usize].generation = u32::MAX;
+        let last: Handle<u32> = Handle{ index: first.index(), generation: u32::MAX, _type: PhantomData };
+
+        // Removing at the last generation wraps around to 0 instead of overflowing
+        assert!(registry.remove(last));
+        let wrapped: Handle<u32> = registry.insert(2);
+        assert_eq!(wrapped.generation(), 0);
+        assert_eq!(registry.get(wrapped), Some(&2));
+        assert_eq!(registry.get(last), None);
+    }
+}
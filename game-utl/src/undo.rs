@@ -0,0 +1,310 @@
+//  UNDO.rs
+//    by Lut99
+// 
+//  Created:
+//    17 Oct 2026, 05:05:27
+//  Last edited:
+//    17 Oct 2026, 05:49:17
+//  Auto updated?
+//    Yes
+// 
+//  Description:
+//!   Implements an undo/redo stack of reversible commands, grouped into
+//!   transactions.
+// 
+
+use std::collections::VecDeque;
+
+
+/***** LIBRARY *****/
+/// A reversible mutation of some context (e.g., moving an entity in a scene).
+pub trait Command<C> {
+    /// Performs the mutation on the given context.
+    /// 
+    /// # Arguments
+    /// - `ctx`: The context to mutate.
+    fn apply(&mut self, ctx: &mut C);
+
+    /// Undoes a previous `Command::apply()` on the given context.
+    /// 
+    /// # Arguments
+    /// - `ctx`: The context to restore.
+    fn revert(&mut self, ctx: &mut C);
+
+    /// Returns a human-readable description of the command (e.g., for an "Undo ..." menu entry).
+    fn name(&self) -> &str;
+}
+
+
+
+/// A group of commands that are undone and redone as one.
+struct Transaction<C> {
+    /// The description of the transaction.
+    name     : String,
+    /// The commands, in the order in which they were applied.
+    commands : Vec<Box<dyn Command<C>>>,
+}
+
+
+
+/// A stack of reversible commands, grouped into transactions (typically one per user action).
+pub struct UndoStack<C> {
+    /// The transactions that may be undone, most recent last.
+    undo  : VecDeque<Transaction<C>>,
+    /// The transactions that may be redone, most recently undone last.
+    redo  : Vec<Transaction<C>>,
+    /// The transaction that is currently being recorded, if any.
+    open  : Option<Transaction<C>>,
+    /// How many times `UndoStack::begin()` has been called without a matching `UndoStack::commit()`.
+    depth : usize,
+    /// The maximum number of transactions we remember.
+    limit : usize,
+}
+
+impl<C> UndoStack<C> {
+    /// Constructor for the UndoStack.
+    /// 
+    /// # Arguments
+    /// - `limit`: The maximum number of transactions that can be undone. Older ones are forgotten.
+    /// 
+    /// # Returns
+    /// A new, empty UndoStack.
+    #[inline]
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo  : VecDeque::new(),
+            redo  : vec![],
+            open  : None,
+            depth : 0,
+            limit,
+        }
+    }
+
+
+
+    /// Starts a transaction, so that all commands executed until the matching `UndoStack::commit()` are undone as one.
+    /// 
+    /// Transactions may be nested; commands then belong to the outermost one.
+    /// 
+    /// # Arguments
+    /// - `name`: The description of the transaction. Ignored for nested transactions.
+    pub fn begin<S: Into<String>>(&mut self, name: S) {
+        if self.depth == 0 { self.open = Some(Transaction{ name: name.into(), commands: vec![] }); }
+        self.depth += 1;
+    }
+
+    /// Ends the transaction started by the matching `UndoStack::begin()`.
+    /// 
+    /// Once the outermost transaction is committed, it can be undone. Empty transactions are dropped.
+    /// 
+    /// # Panics
+    /// This function panics if no transaction was started.
+    pub fn commit(&mut self) {
+        if self.depth == 0 { panic!("Cannot commit a transaction that was never started"); }
+        self.depth -= 1;
+        if self.depth == 0 {
+            let transaction: Transaction<C> = self.open.take().unwrap();
+            if !transaction.commands.is_empty() { self.push(transaction); }
+        }
+    }
+
+    /// Applies the given command and records it.
+    /// 
+    /// If a transaction is open, the command becomes part of it; otherwise it forms a transaction of its own. Either way, everything that could be redone is forgotten.
+    /// 
+    /// # Arguments
+    /// - `command`: The Command to apply.
+    /// - `ctx`: The context to apply it to.
+    pub fn execute<T: 'static + Command<C>>(&mut self, mut command: T, ctx: &mut C) {
+        command.apply(ctx);
+        self.redo.clear();
+        match self.open.as_mut() {
+            Some(transaction) => { transaction.commands.push(Box::new(command)); },
+            None              => {
+                let name: String = command.name().into();
+                self.push(Transaction{ name, commands: vec![ Box::new(command) ] });
+            },
+        }
+    }
+
+    /// Undoes the most recent transaction.
+    /// 
+    /// # Arguments
+    /// - `ctx`: The context to restore.
+    /// 
+    /// # Returns
+    /// Whether there was anything to undo.
+    /// 
+    /// # Panics
+    /// This function panics if a transaction is still open.
+    pub fn undo(&mut self, ctx: &mut C) -> bool {
+        if self.depth > 0 { panic!("Cannot undo while a transaction is open"); }
+        let mut transaction: Transaction<C> = match self.undo.pop_back() {
+            Some(transaction) => transaction,
+            None              => { return false; }
+        };
+        for command in transaction.commands.iter_mut().rev() {
+            command.revert(ctx);
+        }
+        self.redo.push(transaction);
+        true
+    }
+
+    /// Redoes the most recently undone transaction.
+    /// 
+    /// # Arguments
+    /// - `ctx`: The context to apply the transaction to again.
+    /// 
+    /// # Returns
+    /// Whether there was anything to redo.
+    /// 
+    /// # Panics
+    /// This function panics if a transaction is still open.
+    pub fn redo(&mut self, ctx: &mut C) -> bool {
+        if self.depth > 0 { panic!("Cannot redo while a transaction is open"); }
+        let mut transaction: Transaction<C> = match self.redo.pop() {
+            Some(transaction) => transaction,
+            None              => { return false; }
+        };
+        for command in transaction.commands.iter_mut() {
+            command.apply(ctx);
+        }
+        self.undo.push_back(transaction);
+        true
+    }
+
+    /// Forgets all transactions (e.g., after loading another scene).
+    /// 
+    /// # Panics
+    /// This function panics if a transaction is still open.
+    pub fn clear(&mut self) {
+        if self.depth > 0 { panic!("Cannot clear while a transaction is open"); }
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+
+
+    /// Pushes a finished transaction on the undo stack, forgetting the oldest one if we exceed the limit.
+    fn push(&mut self, transaction: Transaction<C>) {
+        self.undo.push_back(transaction);
+        if self.undo.len() > self.limit { self.undo.pop_front(); }
+    }
+
+
+
+    /// Returns the description of the transaction that `UndoStack::undo()` would undo, if any.
+    #[inline]
+    pub fn undo_name(&self) -> Option<&str> { self.undo.back().map(|t| t.name.as_str()) }
+
+    /// Returns the description of the transaction that `UndoStack::redo()` would redo, if any.
+    #[inline]
+    pub fn redo_name(&self) -> Option<&str> { self.redo.last().map(|t| t.name.as_str()) }
+
+    /// Returns whether there is anything to undo.
+    #[inline]
+    pub fn can_undo(&self) -> bool { !self.undo.is_empty() }
+
+    /// Returns whether there is anything to redo.
+    #[inline]
+    pub fn can_redo(&self) -> bool { !self.redo.is_empty() }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    /// Appends a value to a log, so the tests can see the order in which commands ran.
+    struct Push(u32);
+    impl Command<Vec<u32>> for Push {
+        fn apply(&mut self, ctx: &mut Vec<u32>) { ctx.push(self.0); }
+        fn revert(&mut self, ctx: &mut Vec<u32>) { assert_eq!(ctx.pop(), Some(self.0), "commands reverted out of order"); }
+        fn name(&self) -> &str { "push" }
+    }
+
+
+    #[test]
+    fn test_nested_transactions_fold() {
+        let mut stack: UndoStack<Vec<u32>> = UndoStack::new(10);
+        let mut ctx: Vec<u32> = vec![];
+
+        stack.begin("outer");
+        stack.execute(Push(1), &mut ctx);
+        stack.begin("inner");
+        stack.execute(Push(2), &mut ctx);
+        stack.commit();
+        assert!(!stack.can_undo());
+        stack.execute(Push(3), &mut ctx);
+        stack.commit();
+
+        // Everything is undone as a single transaction, newest command first
+        assert_eq!(stack.undo_name(), Some("outer"));
+        assert!(stack.undo(&mut ctx));
+        assert!(ctx.is_empty());
+        assert!(!stack.can_undo());
+
+        assert!(stack.redo(&mut ctx));
+        assert_eq!(ctx, vec![ 1, 2, 3 ]);
+    }
+
+    #[test]
+    fn test_execute_clears_redo() {
+        let mut stack: UndoStack<Vec<u32>> = UndoStack::new(10);
+        let mut ctx: Vec<u32> = vec![];
+
+        stack.execute(Push(1), &mut ctx);
+        stack.execute(Push(2), &mut ctx);
+        assert!(stack.undo(&mut ctx));
+        assert!(stack.can_redo());
+
+        stack.execute(Push(3), &mut ctx);
+        assert!(!stack.can_redo());
+        assert!(!stack.redo(&mut ctx));
+        assert_eq!(ctx, vec![ 1, 3 ]);
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut stack: UndoStack<Vec<u32>> = UndoStack::new(2);
+        let mut ctx: Vec<u32> = vec![];
+        for i in 0..4 { stack.execute(Push(i), &mut ctx); }
+
+        // Only the two most recent transactions are remembered
+        assert!(stack.undo(&mut ctx));
+        assert!(stack.undo(&mut ctx));
+        assert!(!stack.undo(&mut ctx));
+        assert_eq!(ctx, vec![ 0, 1 ]);
+
+        // A limit of zero remembers nothing
+        let mut stack: UndoStack<Vec<u32>> = UndoStack::new(0);
+        stack.execute(Push(4), &mut ctx);
+        assert!(!stack.can_undo());
+        assert!(!stack.undo(&mut ctx));
+        assert_eq!(ctx, vec![ 0, 1, 4 ]);
+    }
+
+    #[test]
+    fn test_empty_transactions_are_dropped() {
+        let mut stack: UndoStack<Vec<u32>> = UndoStack::new(10);
+        let mut ctx: Vec<u32> = vec![];
+        stack.execute(Push(1), &mut ctx);
+
+        stack.begin("nothing");
+        stack.begin("still nothing");
+        stack.commit();
+        stack.commit();
+        assert_eq!(stack.undo_name(), Some("push"));
+    }
+
+    #[test]
+    #[should_panic(expected = "never started")]
+    fn test_commit_without_begin() {
+        UndoStack::<Vec<u32>>::new(10).commit();
+    }
+}